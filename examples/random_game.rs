@@ -37,7 +37,7 @@ fn main() {
     game = game.randomize(0.2);
 
     loop {
-        println!("{}", game.to_string());
+        println!("{}", game);
         game.tick();
         thread::sleep(Duration::from_millis(SLEEP_TIME));
     }
@@ -1,20 +1,32 @@
+use std::error::Error;
 use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
 use std::sync::mpsc;
-use std::{thread, time::Duration};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use clap::{Parser, ValueEnum};
-use game_of_life::game::cell::Cell;
-use game_of_life::game::{CellBoardCreationError, FormatErrorVariant};
+use game_of_life::apgcode::{self, ObjectKind};
+use game_of_life::census::{Census, CensusSearch};
+use game_of_life::game::cell::{Action, Cell};
+use game_of_life::game::rule::{Grid, Rule};
+use game_of_life::game::soup::Soup;
+use game_of_life::game::{CellBoard, PatternFormat};
 
-use crate::commands::CliCommand;
-use crossterm::event::{KeyCode, KeyEventKind};
+use crate::commands::{CliCommand, Command, GameArgs, RunCommand, SearchCommand};
+use crossterm::event::{
+    DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEventKind, MouseButton, MouseEventKind,
+};
 use game_of_life::game::{Game, cell::Slot};
+use game_of_life::period;
 use ratatui::widgets::{Block, Paragraph, Widget};
 use ratatui::{crossterm, prelude::*};
 
-#[derive(Clone, Copy, ValueEnum)]
+#[derive(Clone, Copy, Default, ValueEnum)]
 enum SpeedVariant {
     Slow,
+    #[default]
     Normal,
     Fast,
 }
@@ -42,7 +54,7 @@ impl Speed {
 }
 
 impl SpeedVariant {
-    pub fn to_duration(&self) -> Duration {
+    pub fn to_duration(self) -> Duration {
         match self {
             SpeedVariant::Slow => Duration::from_millis(500),
             SpeedVariant::Normal => Duration::from_millis(100),
@@ -78,12 +90,6 @@ impl SpeedVariant {
     }
 }
 
-impl Default for SpeedVariant {
-    fn default() -> Self {
-        Self::Normal
-    }
-}
-
 impl Default for Speed {
     fn default() -> Self {
         Self::Unpaused(SpeedVariant::default())
@@ -103,23 +109,74 @@ impl std::fmt::Display for Speed {
     }
 }
 
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
 struct GameWidget {
     game: Game,
     speed_when_unpaused: SpeedVariant,
     speed: Speed,
+    soup: Soup,
+    // seed of the random soup on the board, shown so that it can be rerun with
+    // --seed, None once the board is cleared or if it was loaded from a file
+    seed: Option<u64>,
+    // position of the edit cursor, None while not in edit mode
+    cursor: Option<Slot>,
+    // cell state painted while dragging the mouse, None while no button is held
+    paint: Option<Cell>,
+    save_format: SaveFormat,
+    save_dir: PathBuf,
+    // outcome of the last save, shown below the legend
+    status: Option<String>,
+    // counts of the objects on the board by kind, shown in the bottom border of
+    // the board while Some, short enough for the default 20 cell width
+    objects: Option<ObjectCounts>,
+}
+
+struct ObjectCounts {
+    // normalized hash and top left corner of the board the objects were counted
+    // on, to tell when they need counting again
+    board: (u64, (i64, i64)),
+    // when the board was last compared with the one the objects were counted on
+    checked_at: Instant,
+    summary: String,
 }
 
+// how often the objects are counted again while the game runs, counting them takes
+// far longer than a generation on large boards
+const OBJECTS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
 impl GameWidget {
-    pub fn new(game: Game, speed: Speed) -> Self {
+    pub fn new(
+        game: Game,
+        speed: Speed,
+        soup: Soup,
+        seed: Option<u64>,
+        save_format: SaveFormat,
+        save_dir: PathBuf,
+    ) -> Self {
         let speed_when_unpaused = match speed {
             Speed::Paused => SpeedVariant::Normal,
             Speed::Unpaused(speed_variant) => speed_variant,
         };
 
         Self {
-            game,
+            game: game.with_period_detection(),
             speed,
             speed_when_unpaused,
+            soup,
+            seed,
+            cursor: None,
+            paint: None,
+            save_format,
+            save_dir,
+            status: None,
+            objects: None,
         }
     }
     pub fn draw(&self, frame: &mut Frame) {
@@ -130,34 +187,6 @@ impl GameWidget {
         self.game.tick();
     }
 
-    pub fn from_file(path: String, speed: Speed) -> Self {
-        let game = match Game::from_file(path.clone()) {
-            Ok(game) => game,
-            Err(error) => {
-                let error_message = match error {
-                    CellBoardCreationError::FileError => {
-                        format!("error opening file '{}', does it exist?", path)
-                    }
-                    CellBoardCreationError::FormatError(format_error) => match format_error {
-                        FormatErrorVariant::EmptyRow => "empty rows are not allowed".to_owned(),
-                        FormatErrorVariant::RowLengthMismatch { row_index } => {
-                            format!(
-                                "row at index {} does not match previous row lengths",
-                                row_index
-                            )
-                        }
-                        FormatErrorVariant::UnrecognizedCharacter(c) => {
-                            format!("unrecognized character encountered: {}", c)
-                        }
-                    },
-                };
-                panic!("{}", error_message);
-            }
-        };
-
-        Self::new(game, speed)
-    }
-
     pub fn regulate_speed(&mut self, speed_action: SpeedAction) -> SpeedActionEffect {
         self.speed.regulate(speed_action)
     }
@@ -172,6 +201,280 @@ impl GameWidget {
     pub fn speed(&self) -> Speed {
         self.speed
     }
+
+    pub fn is_editing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    // entering edit mode pauses the simulation, returns whether the speed changed;
+    // a board without cells has no slot to put the cursor on
+    pub fn toggle_edit_mode(&mut self) -> SpeedActionEffect {
+        match self.cursor {
+            Some(_) => {
+                self.cursor = None;
+                SpeedActionEffect::Unchanged
+            }
+            None if self.game.height() == 0 || self.game.width() == 0 => {
+                self.status = Some("Cannot edit an empty board".to_owned());
+                SpeedActionEffect::Unchanged
+            }
+            None => {
+                self.cursor = Some(Slot(self.game.height() / 2, self.game.width() / 2));
+                match self.speed {
+                    Speed::Paused => SpeedActionEffect::Unchanged,
+                    Speed::Unpaused(_) => {
+                        self.toggle_pause();
+                        SpeedActionEffect::Changed
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn move_cursor(&mut self, direction: Direction) {
+        if let Some(Slot(row, col)) = self.cursor {
+            self.cursor = Some(match direction {
+                Direction::Up => Slot(row.saturating_sub(1), col),
+                Direction::Down => Slot((row + 1).min(self.game.height().saturating_sub(1)), col),
+                Direction::Left => Slot(row, col.saturating_sub(1)),
+                Direction::Right => Slot(row, (col + 1).min(self.game.width().saturating_sub(1))),
+            });
+        }
+    }
+
+    // cells of multi-color rules cycle through the species before they die
+    pub fn toggle_cell(&mut self, slot: Slot) {
+        let species = self.game.rule().species();
+        let action = match self.game.get_slot(slot).species() {
+            Some(_) if species == 1 => Action::Die,
+            Some(current) if current < species => Action::Spawn(current + 1),
+            Some(_) => Action::Die,
+            None if species == 1 => Action::Live,
+            None => Action::Spawn(1),
+        };
+        self.game.apply_action(slot, action);
+    }
+
+    pub fn toggle_cell_under_cursor(&mut self) {
+        if let Some(slot) = self.cursor {
+            self.toggle_cell(slot);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.game.clear();
+        self.seed = None;
+    }
+
+    // a soup region may not fit the board of a loaded pattern file
+    pub fn randomize(&mut self) {
+        if let Err(error) = self.soup.region_on(self.game.height(), self.game.width()) {
+            self.status = Some(format!("Could not randomize: {}", error));
+            return;
+        }
+        let seed = rand::random();
+        self.game = std::mem::take(&mut self.game)
+            .randomize_soup_seeded(&self.soup, seed)
+            .expect("the soup region fits the board");
+        self.seed = Some(seed);
+    }
+
+    pub fn step(&mut self) {
+        if let Speed::Paused = self.speed {
+            self.tick();
+        }
+    }
+
+    pub fn step_back(&mut self) {
+        if let Speed::Paused = self.speed {
+            self.game.step_back();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self.speed, Speed::Paused)
+    }
+
+    // writes the board to a new file named after the current time in the save
+    // directory
+    pub fn save(&mut self) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.save_dir.join(format!(
+            "life-{}.{}",
+            timestamp,
+            self.save_format.extension()
+        ));
+        let Some(content) = self.game.to_pattern(self.save_format.pattern_format()) else {
+            self.status = Some(format!(
+                "Rule {} cannot be saved as {}",
+                self.game.rule(),
+                self.save_format.extension()
+            ));
+            return;
+        };
+        self.status = Some(match std::fs::write(&path, content) {
+            Ok(()) => format!(
+                "Saved generation {} to {}",
+                self.game.generation(),
+                path.display()
+            ),
+            Err(error) => format!("Could not save to {}: {}", path.display(), error),
+        });
+    }
+
+    pub fn toggle_objects(&mut self) {
+        self.objects = match self.objects {
+            Some(_) => None,
+            None => Some(self.count_objects()),
+        };
+    }
+
+    // counts the objects again if the board changed since they were counted, while
+    // the game runs at most once per refresh interval
+    pub fn refresh_objects(&mut self) {
+        let due = self.objects.as_ref().is_some_and(|objects| {
+            self.is_paused() || objects.checked_at.elapsed() >= OBJECTS_REFRESH_INTERVAL
+        });
+        if !due {
+            return;
+        }
+        let board = self.board_hash();
+        match &mut self.objects {
+            Some(objects) if objects.board == board => objects.checked_at = Instant::now(),
+            _ => self.objects = Some(self.count_objects()),
+        }
+    }
+
+    fn board_hash(&self) -> (u64, (i64, i64)) {
+        period::normalized_hash(
+            self.game
+                .slots_and_cells()
+                .filter(|(_, cell)| cell.is_alive())
+                .map(|(Slot(row, col), _)| (row as i64, col as i64)),
+        )
+    }
+
+    fn count_objects(&self) -> ObjectCounts {
+        let summary = match self.game.objects() {
+            Ok(objects) => {
+                let count = |kind: Option<ObjectKind>| {
+                    objects
+                        .iter()
+                        .filter(|object| object.classification.kind() == kind)
+                        .count()
+                };
+                format!(
+                    "Still: {} | Osc: {} | Ships: {} | Other: {}",
+                    count(Some(ObjectKind::StillLife)),
+                    count(Some(ObjectKind::Oscillator)),
+                    count(Some(ObjectKind::Spaceship)),
+                    count(None)
+                )
+            }
+            Err(_) => String::from("Objects: Life-like rules only"),
+        };
+        ObjectCounts {
+            board: self.board_hash(),
+            checked_at: Instant::now(),
+            summary,
+        }
+    }
+
+    fn game_area(&self, area: Rect) -> Rect {
+        let board_width = (self.game.width() * 2 + 2) as u16 // 2 chars per cell + 2 for borders
+            + self.row_offset(0);
+        let board_height = (self.game.height() + 2) as u16; // 1 row per cell + 2 for borders
+
+        Rect {
+            x: area.x + (area.width.saturating_sub(board_width)) / 2,
+            y: area.y + (area.height.saturating_sub(board_height)) / 2,
+            width: board_width.min(area.width),
+            height: board_height.min(area.height),
+        }
+    }
+
+    // the slot rendered at the given terminal position, if any
+    fn slot_at(&self, area: Rect, column: u16, row: u16) -> Option<Slot> {
+        let inner = self.game_area(area).inner(Margin::new(1, 1));
+        if !inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        let board_row = (row - inner.y) as usize;
+        let board_column = (column - inner.x).checked_sub(self.row_offset(board_row))? / 2;
+        let slot = Slot(board_row, board_column as usize);
+        (slot.0 < self.game.height() && slot.1 < self.game.width()).then_some(slot)
+    }
+
+    // hexagonal boards are stored skewed, every row is drawn half a cell left of
+    // the row above so that every cell touches its six neighbors
+    fn row_offset(&self, row: usize) -> u16 {
+        match self.game.grid() {
+            Grid::Hexagonal => self.game.height().saturating_sub(row + 1) as u16,
+            Grid::Square | Grid::Triangular => 0,
+        }
+    }
+
+    // triangles pointing up and down on triangular boards, squares otherwise
+    fn cell_symbol(&self, Slot(row, col): Slot) -> &'static str {
+        match self.game.grid() {
+            Grid::Triangular if (row + col).is_multiple_of(2) => "◢◣",
+            Grid::Triangular => "◥◤",
+            Grid::Square | Grid::Hexagonal => "██",
+        }
+    }
+
+    // a press toggles the cell under the pointer, dragging paints every cell it
+    // passes over with the state the pressed cell was toggled to
+    pub fn point(&mut self, pointer_action: PointerAction, area: Rect, column: u16, row: u16) {
+        let slot = self.slot_at(area, column, row);
+        match (pointer_action, slot) {
+            (PointerAction::Press, Some(slot)) => {
+                self.toggle_cell(slot);
+                self.paint = Some(self.game.get_slot(slot));
+            }
+            (PointerAction::Drag, Some(slot)) => {
+                if let Some(paint) = self.paint
+                    && paint != self.game.get_slot(slot)
+                {
+                    let action = match paint {
+                        Cell::Alive => Action::Live,
+                        Cell::Species(species) => Action::Spawn(species),
+                        Cell::Dead | Cell::Dying(_) => Action::Die,
+                    };
+                    self.game.apply_action(slot, action);
+                }
+            }
+            (PointerAction::Release, _) => self.paint = None,
+            (_, None) => {}
+        }
+    }
+}
+
+// the species of multi-color rules, species 1 first
+const SPECIES_COLORS: [(Color, &str); 4] = [
+    (Color::LightRed, "Red"),
+    (Color::LightGreen, "Green"),
+    (Color::LightCyan, "Cyan"),
+    (Color::LightMagenta, "Magenta"),
+];
+
+fn species_color(species: u8) -> Color {
+    (species as usize)
+        .checked_sub(1)
+        .and_then(|index| SPECIES_COLORS.get(index))
+        .map_or(Color::White, |(color, _)| *color)
+}
+
+// dying cells of Generations rules fade from blue towards the background
+fn dying_color(age: u8, states: u8) -> Color {
+    let dying_states = states.saturating_sub(2).max(1) as u32;
+    let fade = |brightest: u32| {
+        (brightest * (dying_states + 1).saturating_sub(age as u32) / dying_states) as u8
+    };
+    Color::Rgb(fade(60), fade(110), fade(255).max(40))
 }
 
 impl Widget for &GameWidget {
@@ -179,46 +482,82 @@ impl Widget for &GameWidget {
     where
         Self: Sized,
     {
-        let board_width = (self.game.width() * 2 + 2) as u16; // 2 chars per cell + 2 for borders
-        let board_height = (self.game.height() + 2) as u16; // 1 row per cell + 2 for borders
+        let game_area = self.game_area(area);
 
-        let game_area = Rect {
-            x: area.x + (area.width.saturating_sub(board_width)) / 2,
-            y: area.y + (area.height.saturating_sub(board_height)) / 2,
-            width: board_width.min(area.width),
-            height: board_height.min(area.height),
-        };
+        let mut title = format!(
+            "{}Generation: {} | Speed: {} | Rule: {} | Topology: {}",
+            if self.is_editing() { "[Edit] " } else { "" },
+            self.game.generation(),
+            self.speed,
+            self.game.rule(),
+            self.game.topology()
+        );
+        // everything needed to rerun the soup
+        if let Some(seed) = self.seed {
+            title += &format!(" | Seed: {} | Symmetry: {}", seed, self.soup.symmetry());
+            if let Some(region) = self.soup.region() {
+                title += &format!(" | Region: {}", region);
+            }
+            if let Some((gradient, end_density)) = self.soup.gradient() {
+                title += &format!(" | Gradient: {} to {}", gradient, end_density);
+            }
+        }
+        if self.game.rule().species() > 1 {
+            for (population, (_, name)) in self.game.species_population().iter().zip(SPECIES_COLORS)
+            {
+                title += &format!(" | {}: {}", name, population);
+            }
+        }
+        if self.game.population() == 0 {
+            title += " | Died out";
+        } else if let Some(cycle) = self.game.cycle() {
+            title += &format!(" | {}", cycle);
+        }
 
-        Block::bordered()
-            .title(format!(
-                "Generation: {} | Speed: {}",
-                self.game.generation(),
-                self.speed
-            ))
-            .render(game_area, buf);
+        let block = Block::bordered().title(title);
+        match &self.objects {
+            Some(objects) => block.title_bottom(objects.summary.as_str()),
+            None => block,
+        }
+        .render(game_area, buf);
 
         let inner = game_area.inner(Margin::new(1, 1));
+        let states = self.game.rule().states();
+        let multi_color = self.game.rule().species() > 1;
 
         for (Slot(y, x), cell) in self.game.slots_and_cells() {
-            let screen_x = inner.x + (x as u16) * 2; // 2 chars wide per cell
+            let screen_x = inner.x + (x as u16) * 2 + self.row_offset(y); // 2 chars wide per cell
             let screen_y = inner.y + y as u16;
 
-            let (symbol, style) = match cell {
-                Cell::Alive => ("██", Style::default().fg(Color::White)),
+            let symbol = self.cell_symbol(Slot(y, x));
+            let (symbol, mut style) = match cell {
+                Cell::Alive if multi_color => (symbol, Style::default().fg(species_color(1))),
+                Cell::Alive => (symbol, Style::default().fg(Color::White)),
+                Cell::Species(species) => (symbol, Style::default().fg(species_color(species))),
                 Cell::Dead => ("  ", Style::default().fg(Color::Black)),
+                Cell::Dying(age) => (symbol, Style::default().fg(dying_color(age, states))),
             };
+            if self.cursor == Some(Slot(y, x)) {
+                style = style.bg(Color::Yellow);
+            }
 
             buf.set_string(screen_x, screen_y, symbol, style);
         }
 
         let legend_y = game_area.y + game_area.height + 1;
         if legend_y < area.height {
-            let legend_text =
-                "q: Quit  |  ↑/→: Speed Up  |  ↓/←: Slow Down  |  Space: Pause/Unpause";
+            let legend_text = if self.is_editing() {
+                "e: Stop Editing  |  ←↑↓→: Move  |  Enter: Toggle Cell  |  c: Clear  |  r: Randomize  |  b/s: Step Back/Forward"
+            } else if self.is_paused() {
+                "q: Quit  |  Space: Unpause  |  ←/b: Step Back  |  →/s: Step Forward  |  e: Edit  |  w: Save  |  o: Objects"
+            } else {
+                "q: Quit  |  ↑/→: Speed Up  |  ↓/←: Slow Down  |  Space: Pause/Unpause  |  e: Edit  |  w: Save  |  o: Objects"
+            };
+            let legend_width = legend_text.chars().count() as u16;
             let legend_area = Rect {
-                x: area.x + (area.width.saturating_sub(legend_text.len() as u16)) / 2,
+                x: area.x + (area.width.saturating_sub(legend_width)) / 2,
                 y: legend_y,
-                width: legend_text.len() as u16,
+                width: legend_width,
                 height: 1,
             };
 
@@ -226,6 +565,20 @@ impl Widget for &GameWidget {
                 .style(Style::default().fg(Color::DarkGray))
                 .render(legend_area, buf);
         }
+
+        let status_y = legend_y + 1;
+        if let Some(status) = &self.status
+            && status_y < area.height
+        {
+            let status_width = (status.chars().count() as u16).min(area.width);
+            let status_area = Rect {
+                x: area.x + (area.width - status_width) / 2,
+                y: status_y,
+                width: status_width,
+                height: 1,
+            };
+            Paragraph::new(status.as_str()).render(status_area, buf);
+        }
     }
 }
 
@@ -243,7 +596,25 @@ enum UserAction {
     Quit,
     TogglePause,
     RegulateSpeed(SpeedAction),
+    // arrow keys regulate the speed, or move the cursor in edit mode
+    Arrow(Direction),
+    ToggleEditMode,
+    ToggleCell,
+    // mouse action at a (column, row) terminal position
+    Pointer(PointerAction, u16, u16),
+    Clear,
+    Randomize,
+    Step,
+    StepBack,
+    Save,
+    ToggleObjects,
+}
+enum PointerAction {
+    Press,
+    Drag,
+    Release,
 }
+
 enum UpdateEvent {
     Tick,
     Input(UserAction),
@@ -260,11 +631,15 @@ fn handle_tick(mut speed: Speed, tx: mpsc::Sender<UpdateEvent>, control_rx: mpsc
                     tx.send(UpdateEvent::Tick)
                         .expect("mpsc channel to work correctly");
                 }
-                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return,
             }
         }
         while let Speed::Paused = speed {
-            speed = control_rx.recv().expect("mpsc channel to work");
+            match control_rx.recv() {
+                Ok(new_speed) => speed = new_speed,
+                // the main loop has exited
+                Err(mpsc::RecvError) => return,
+            }
         }
     }
 }
@@ -272,21 +647,55 @@ fn handle_tick(mut speed: Speed, tx: mpsc::Sender<UpdateEvent>, control_rx: mpsc
 fn handle_user_input(tx: mpsc::Sender<UpdateEvent>) {
     loop {
         match crossterm::event::read().unwrap() {
-            crossterm::event::Event::Key(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    match key_event.code {
-                        KeyCode::Esc | KeyCode::Char('q') => {
-                            tx.send(UpdateEvent::Input(UserAction::Quit))
-                        }
-                        KeyCode::Left | KeyCode::Down => tx.send(UpdateEvent::Input(
-                            UserAction::RegulateSpeed(SpeedAction::Decrease),
-                        )),
-                        KeyCode::Right | KeyCode::Up => tx.send(UpdateEvent::Input(
-                            UserAction::RegulateSpeed(SpeedAction::Increase),
-                        )),
-                        KeyCode::Char(' ') => tx.send(UpdateEvent::Input(UserAction::TogglePause)),
-                        _ => Ok(()),
+            crossterm::event::Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                match key_event.code {
+                    KeyCode::Esc | KeyCode::Char('q') => {
+                        tx.send(UpdateEvent::Input(UserAction::Quit))
+                    }
+                    KeyCode::Up => tx.send(UpdateEvent::Input(UserAction::Arrow(Direction::Up))),
+                    KeyCode::Down => {
+                        tx.send(UpdateEvent::Input(UserAction::Arrow(Direction::Down)))
                     }
+                    KeyCode::Left => {
+                        tx.send(UpdateEvent::Input(UserAction::Arrow(Direction::Left)))
+                    }
+                    KeyCode::Right => {
+                        tx.send(UpdateEvent::Input(UserAction::Arrow(Direction::Right)))
+                    }
+                    KeyCode::Char('-') => tx.send(UpdateEvent::Input(UserAction::RegulateSpeed(
+                        SpeedAction::Decrease,
+                    ))),
+                    KeyCode::Char('+') => tx.send(UpdateEvent::Input(UserAction::RegulateSpeed(
+                        SpeedAction::Increase,
+                    ))),
+                    KeyCode::Char(' ') => tx.send(UpdateEvent::Input(UserAction::TogglePause)),
+                    KeyCode::Char('e') => tx.send(UpdateEvent::Input(UserAction::ToggleEditMode)),
+                    KeyCode::Enter | KeyCode::Char('x') => {
+                        tx.send(UpdateEvent::Input(UserAction::ToggleCell))
+                    }
+                    KeyCode::Char('c') => tx.send(UpdateEvent::Input(UserAction::Clear)),
+                    KeyCode::Char('r') => tx.send(UpdateEvent::Input(UserAction::Randomize)),
+                    KeyCode::Char('s') => tx.send(UpdateEvent::Input(UserAction::Step)),
+                    KeyCode::Char('b') => tx.send(UpdateEvent::Input(UserAction::StepBack)),
+                    KeyCode::Char('w') => tx.send(UpdateEvent::Input(UserAction::Save)),
+                    KeyCode::Char('o') => tx.send(UpdateEvent::Input(UserAction::ToggleObjects)),
+                    _ => Ok(()),
+                }
+                .expect("mpsc channel to work correctly")
+            }
+            crossterm::event::Event::Mouse(mouse_event) => {
+                let pointer_action = match mouse_event.kind {
+                    MouseEventKind::Down(MouseButton::Left) => Some(PointerAction::Press),
+                    MouseEventKind::Drag(MouseButton::Left) => Some(PointerAction::Drag),
+                    MouseEventKind::Up(MouseButton::Left) => Some(PointerAction::Release),
+                    _ => None,
+                };
+                if let Some(pointer_action) = pointer_action {
+                    tx.send(UpdateEvent::Input(UserAction::Pointer(
+                        pointer_action,
+                        mouse_event.column,
+                        mouse_event.row,
+                    )))
                     .expect("mpsc channel to work correctly")
                 }
             }
@@ -295,29 +704,317 @@ fn handle_user_input(tx: mpsc::Sender<UpdateEvent>) {
     }
 }
 
-fn main() -> io::Result<()> {
-    let command = CliCommand::parse();
-
-    let CliCommand {
+// the game with the seed of its random soup, None if it was loaded from a file or
+// an apgcode
+fn create_game(game_args: GameArgs) -> Result<(Game, Option<u64>), Box<dyn Error>> {
+    let soup = game_args.soup();
+    let GameArgs {
         from_file,
+        apgcode,
         height,
         width,
-        alive_probability,
+        seed,
+        rule,
+        topology,
+        ..
+    } = game_args;
+
+    let (game, seed) = if let Some(file_path) = from_file {
+        let game = Game::from_file(file_path)?;
+        let game = match rule {
+            Some(rule) => game.with_rule(rule),
+            None => game,
+        };
+        (game, None)
+    } else if let Some(apgcode) = apgcode {
+        let pattern = apgcode::decode(&apgcode)?;
+        let game = Game::from_cell_board(centered(&pattern, height as usize, width as usize))
+            .with_rule(rule.unwrap_or_default());
+        (game, None)
+    } else {
+        let seed = seed.unwrap_or_else(rand::random);
+        let game = Game::new(height as usize, width as usize)
+            .with_rule(rule.unwrap_or_default())
+            .randomize_soup_seeded(&soup, seed)?;
+        (game, Some(seed))
+    };
+    let game = match topology {
+        Some(topology) => game.with_topology(topology),
+        None => game,
+    };
+    if !game
+        .grid()
+        .fits(game.topology(), game.height(), game.width())
+    {
+        return Err(format!(
+            "a {}x{} {} does not fit the {} grid of {}",
+            game.height(),
+            game.width(),
+            game.topology(),
+            game.grid(),
+            game.rule()
+        )
+        .into());
+    }
+    Ok((game, seed))
+}
+
+// the pattern in the middle of a board of the given size, or of its own size if
+// it is larger
+fn centered(pattern: &CellBoard, height: usize, width: usize) -> CellBoard {
+    let mut cell_board = CellBoard::new(height.max(pattern.height()), width.max(pattern.width()));
+    let top = (cell_board.height() - pattern.height()) / 2;
+    let left = (cell_board.width() - pattern.width()) / 2;
+    for (Slot(row, col), cell) in pattern.slots_and_cells() {
+        cell_board.set_slot(Slot(top + row, left + col), cell);
+    }
+    cell_board
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SaveFormat {
+    Rle,
+    Cells,
+    Life105,
+    Life106,
+    Mc,
+}
+
+impl SaveFormat {
+    fn pattern_format(self) -> PatternFormat {
+        match self {
+            SaveFormat::Rle => PatternFormat::Rle,
+            SaveFormat::Cells => PatternFormat::Plaintext,
+            SaveFormat::Life105 => PatternFormat::Life105,
+            SaveFormat::Life106 => PatternFormat::Life106,
+            SaveFormat::Mc => PatternFormat::Macrocell,
+        }
+    }
+
+    // Life 1.0x files are told apart by their header, not by the extension
+    fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Rle => "rle",
+            SaveFormat::Cells => "cells",
+            SaveFormat::Life105 | SaveFormat::Life106 => "lif",
+            SaveFormat::Mc => "mc",
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Apgcode,
+}
+
+// a JSON string literal, with quotes, backslashes and control characters escaped
+fn json_string(value: impl std::fmt::Display) -> String {
+    let mut json = String::from('"');
+    for c in value.to_string().chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CensusFormat {
+    Csv,
+    Json,
+}
+
+impl CensusFormat {
+    fn write(self, census: &Census, rule: Rule, first_seed: u64) -> String {
+        let kind =
+            |apgcode: &str| ObjectKind::of(apgcode).map_or(String::new(), |kind| kind.to_string());
+        match self {
+            CensusFormat::Csv => {
+                let mut csv = String::from("apgcode,kind,count\n");
+                for (apgcode, count) in census.objects() {
+                    csv += &format!("{},{},{}\n", apgcode, kind(apgcode), count);
+                }
+                csv
+            }
+            CensusFormat::Json => {
+                let objects: Vec<String> = census
+                    .objects()
+                    .into_iter()
+                    .map(|(apgcode, count)| {
+                        format!(
+                            "{{\"apgcode\":{},\"kind\":{},\"count\":{}}}",
+                            json_string(apgcode),
+                            json_string(kind(apgcode)),
+                            count
+                        )
+                    })
+                    .collect();
+                format!(
+                    "{{\"rule\":{},\"first_seed\":{},\"soups\":{},\"unstabilized\":{},\"unclassified\":{},\"objects\":[{}]}}\n",
+                    json_string(rule),
+                    first_seed,
+                    census.soups(),
+                    census.unstabilized(),
+                    census.unclassified(),
+                    objects.join(",")
+                )
+            }
+        }
+    }
+}
+
+// runs the soups with consecutive seeds and writes their census, exit code 2 if
+// the search could not be set up or the census not written
+fn run_search(search_command: SearchCommand) -> ExitCode {
+    let soup = search_command.soup();
+    let SearchCommand {
+        rule,
+        soups,
+        seed,
+        size,
+        max_generations,
+        format,
+        output,
+        ..
+    } = search_command;
+
+    let rule = rule.unwrap_or_default();
+    let search = match CensusSearch::new(rule, soup, size as usize, size as usize) {
+        Ok(search) => search.with_max_generations(max_generations),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+    let first_seed = seed.unwrap_or_else(rand::random);
+    let census = search.run((0..soups).map(|soup| first_seed.wrapping_add(soup)));
+
+    let content = format.write(&census, rule, first_seed);
+    match output {
+        Some(path) => {
+            if let Err(error) = std::fs::write(&path, content) {
+                eprintln!("error: could not write to {}: {}", path.display(), error);
+                return ExitCode::from(2);
+            }
+        }
+        None => print!("{}", content),
+    }
+    eprintln!(
+        "Searched {} soups from seed {}: {} unstabilized, {} unclassified objects",
+        census.soups(),
+        first_seed,
+        census.unstabilized(),
+        census.unclassified()
+    );
+    ExitCode::SUCCESS
+}
+
+// exit code 0 if any cell is still alive after the last generation, 1 if the
+// pattern died out and 2 if the game could not be created or the final board has
+// no apgcode
+fn run_headless(run_command: RunCommand) -> ExitCode {
+    let RunCommand {
+        game: game_args,
+        generations,
+        format,
+    } = run_command;
+
+    let mut game = match create_game(game_args) {
+        Ok((game, _)) => game,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return ExitCode::from(2);
+        }
+    };
+    for _ in 0..generations {
+        game.tick();
+    }
+
+    let died_out = game.population() == 0;
+    match format {
+        OutputFormat::Text => {
+            print!("{}", game.to_life());
+            println!("Generation: {}", game.generation());
+            println!("Population: {}", game.population());
+        }
+        OutputFormat::Json => {
+            let rows: Vec<String> = game.to_life().lines().map(json_string).collect();
+            println!(
+                "{{\"generation\":{},\"population\":{},\"died_out\":{},\"rule\":{},\"topology\":{},\"height\":{},\"width\":{},\"board\":[{}]}}",
+                game.generation(),
+                game.population(),
+                died_out,
+                json_string(game.rule()),
+                json_string(game.topology()),
+                game.height(),
+                game.width(),
+                rows.join(",")
+            );
+        }
+        OutputFormat::Apgcode => match game.apgcode() {
+            Ok(apgcode) => println!("{}", apgcode),
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::from(2);
+            }
+        },
+    }
+
+    if died_out {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> io::Result<ExitCode> {
+    let command = CliCommand::parse();
+
+    let CliCommand {
+        game: game_args,
         speed: speed_variant,
+        history,
+        save_format,
+        save_dir,
+        command,
     } = command;
 
+    match command {
+        Some(Command::Run(run_command)) => return Ok(run_headless(run_command)),
+        Some(Command::Search(search_command)) => return Ok(run_search(search_command)),
+        None => {}
+    }
+
     let speed = Speed::Unpaused(speed_variant);
+    let soup = game_args.soup();
 
-    let mut game_widget = if let Some(file_path) = from_file {
-        GameWidget::from_file(file_path, speed)
-    } else {
-        GameWidget::new(
-            Game::new(height as usize, width as usize).randomize(alive_probability),
+    let mut game_widget = match create_game(game_args) {
+        Ok((game, seed)) => GameWidget::new(
+            game.with_history(history),
             speed,
-        )
+            soup,
+            seed,
+            save_format,
+            save_dir,
+        ),
+        Err(error) => {
+            eprintln!("error: {}", error);
+            return Ok(ExitCode::from(2));
+        }
     };
 
     let mut terminal = ratatui::init();
+    let _restore_terminal = RestoreTerminal;
+    crossterm::execute!(io::stdout(), EnableMouseCapture)?;
 
     let (update_tx, update_rx) = mpsc::channel::<UpdateEvent>();
     let update_tx_to_tick = update_tx.clone();
@@ -334,6 +1031,7 @@ fn main() -> io::Result<()> {
     });
 
     loop {
+        game_widget.refresh_objects();
         terminal.draw(|frame| game_widget.draw(frame))?;
         match update_rx.recv().unwrap() {
             UpdateEvent::Tick => game_widget.tick(),
@@ -346,29 +1044,187 @@ fn main() -> io::Result<()> {
                             .expect("mpsc channel to work correctly");
                     }
                 }
+                UserAction::Arrow(direction) if game_widget.is_editing() => {
+                    game_widget.move_cursor(direction)
+                }
+                UserAction::Arrow(Direction::Left) if game_widget.is_paused() => {
+                    game_widget.step_back()
+                }
+                UserAction::Arrow(Direction::Right) if game_widget.is_paused() => {
+                    game_widget.step()
+                }
+                UserAction::Arrow(direction) => {
+                    let speed_action = match direction {
+                        Direction::Up | Direction::Right => SpeedAction::Increase,
+                        Direction::Down | Direction::Left => SpeedAction::Decrease,
+                    };
+                    if let SpeedActionEffect::Changed = game_widget.regulate_speed(speed_action) {
+                        speed_tx
+                            .send(game_widget.speed())
+                            .expect("mpsc channel to work correctly");
+                    }
+                }
                 UserAction::TogglePause => {
                     game_widget.toggle_pause();
                     speed_tx
                         .send(game_widget.speed())
                         .expect("mpsc channel to work correctly");
                 }
+                UserAction::ToggleEditMode => {
+                    if let SpeedActionEffect::Changed = game_widget.toggle_edit_mode() {
+                        speed_tx
+                            .send(game_widget.speed())
+                            .expect("mpsc channel to work correctly");
+                    }
+                }
+                UserAction::ToggleCell => game_widget.toggle_cell_under_cursor(),
+                UserAction::Pointer(pointer_action, column, row) => {
+                    let size = terminal.size()?;
+                    let area = Rect::new(0, 0, size.width, size.height);
+                    game_widget.point(pointer_action, area, column, row);
+                }
+                UserAction::Clear => game_widget.clear(),
+                UserAction::Randomize => game_widget.randomize(),
+                UserAction::Step => game_widget.step(),
+                UserAction::StepBack => game_widget.step_back(),
+                UserAction::Save => game_widget.save(),
+                UserAction::ToggleObjects => game_widget.toggle_objects(),
             },
         }
     }
-    ratatui::restore();
-    Ok(())
+    Ok(ExitCode::SUCCESS)
+}
+
+// hands the terminal back however the event loop ends, also when an error is
+// propagated out of it
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        // nothing is left to report an error to while the terminal is torn down
+        let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+    }
 }
 
 mod commands {
-    use clap::Parser;
+    use clap::{Args, Parser, Subcommand};
+    use game_of_life::game::rule::Rule;
+    use game_of_life::game::soup::{Gradient, Region, Soup, Symmetry};
+    use game_of_life::game::topology::Topology;
 
-    use crate::SpeedVariant;
+    use std::path::PathBuf;
+
+    use crate::{CensusFormat, OutputFormat, SaveFormat, SpeedVariant};
 
     #[derive(Parser)]
+    #[command(args_conflicts_with_subcommands = true)]
     pub struct CliCommand {
-        // initializes the life board from a .life file with the specified path
+        #[command(flatten)]
+        pub game: GameArgs,
+
+        // Simulation speed
+        #[arg(value_enum, long, default_value_t = SpeedVariant::Normal)]
+        pub speed: SpeedVariant,
+
+        // Number of previous generations and cell edits kept for stepping backward
+        // while paused
+        #[arg(long, default_value_t = 1000)]
+        pub history: usize,
+
+        // Format of the files written by the save key
+        #[arg(value_enum, long, default_value_t = SaveFormat::Rle)]
+        pub save_format: SaveFormat,
+
+        // Directory the save key writes its files to
+        #[arg(long, default_value = ".")]
+        pub save_dir: PathBuf,
+
+        #[command(subcommand)]
+        pub command: Option<Command>,
+    }
+
+    #[derive(Subcommand)]
+    pub enum Command {
+        // simulates without the terminal interface and prints the final board
+        Run(RunCommand),
+        // runs many random soups until they stabilize and writes a census of the
+        // objects they leave behind by apgcode, like apgsearch
+        Search(SearchCommand),
+    }
+
+    #[derive(Args)]
+    pub struct RunCommand {
+        #[command(flatten)]
+        pub game: GameArgs,
+
+        // Number of generations to simulate
+        #[arg(long, default_value_t = 100)]
+        pub generations: usize,
+
+        // Output format of the final board
+        #[arg(value_enum, long, default_value_t = OutputFormat::Text)]
+        pub format: OutputFormat,
+    }
+
+    #[derive(Args)]
+    pub struct SearchCommand {
+        // Life-like rule of the soups, defaults to B3/S23
+        #[arg(long)]
+        pub rule: Option<Rule>,
+
+        // Number of soups to run
+        #[arg(long, default_value_t = 100)]
+        pub soups: u64,
+
+        // Seed of the first soup, the following soups use the next seeds, defaults to
+        // a random seed
+        #[arg(long)]
+        pub seed: Option<u64>,
+
+        // Height and width of the soups
+        #[arg(long, default_value_t = 16)]
+        pub size: u8,
+
+        // Probability that a cell of a soup will be initialized as alive
+        #[arg(long, default_value_t = 0.5)]
+        pub alive_probability: f64,
+
+        // Symmetry of the soups: C1, C2, C4, D2, D4 or D8
+        #[arg(long, default_value_t = Symmetry::C1)]
+        pub symmetry: Symmetry,
+
+        // Generations after which a soup that is still changing is counted as
+        // unstabilized
+        #[arg(long, default_value_t = 10000)]
+        pub max_generations: u64,
+
+        // Output format of the census
+        #[arg(value_enum, long, default_value_t = CensusFormat::Csv)]
+        pub format: CensusFormat,
+
+        // File the census is written to, defaults to standard output
+        #[arg(long)]
+        pub output: Option<PathBuf>,
+    }
+
+    impl SearchCommand {
+        pub fn soup(&self) -> Soup {
+            Soup::new(self.alive_probability).with_symmetry(self.symmetry)
+        }
+    }
+
+    #[derive(Args)]
+    pub struct GameArgs {
+        // initializes the life board from a .life, .rle, .cells, .mc or Life 1.05/1.06
+        // file with the specified path
         #[arg(short, long)]
-        pub from_file: Option<String>,
+        pub from_file: Option<PathBuf>,
+
+        // initializes the life board with the object named by an apgcode (e.g. xq4_153
+        // for the glider) in its middle
+        #[arg(long, conflicts_with = "from_file")]
+        pub apgcode: Option<String>,
 
         // height of the life board
         #[arg(long, default_value_t = 20)]
@@ -382,8 +1238,58 @@ mod commands {
         #[arg(long, default_value_t = 0.2)]
         pub alive_probability: f64,
 
-        // Simulation speed
-        #[arg(value_enum, long, default_value_t = SpeedVariant::Normal)]
-        pub speed: SpeedVariant,
+        // Symmetry of the random soup: C1, C2, C4, D2, D4 or D8, C4 and D8 need a
+        // square soup region
+        #[arg(long, default_value_t = Symmetry::C1)]
+        pub symmetry: Symmetry,
+
+        // Rectangle the random soup is restricted to as row,col,height,width (e.g.
+        // 5,5,16,16), the rest of the board starts dead, defaults to the whole board
+        #[arg(long)]
+        pub region: Option<Region>,
+
+        // Direction in which the alive probability changes across the soup region:
+        // horizontal, vertical or radial (from the center outwards)
+        #[arg(long)]
+        pub gradient: Option<Gradient>,
+
+        // Alive probability at the far end of the gradient
+        #[arg(long, default_value_t = 0.0)]
+        pub gradient_end: f64,
+
+        // Seed of the random soup, shown in the title so that a soup can be rerun,
+        // defaults to a random seed
+        #[arg(long)]
+        pub seed: Option<u64>,
+
+        // Life-like rule in B/S notation (e.g. B36/S23) or S/B notation (e.g. 23/3),
+        // a Generations rule with a number of states (e.g. B2/S/C3 or /2/3) or a
+        // Larger than Life rule (e.g. R5,C0,M1,S34..58,B34..45,NM), Hensel notation
+        // selects isotropic non-totalistic rules (e.g. B2-a/S12) and an H or L suffix
+        // the hexagonal or triangular grid, Immigration and QuadLife (optionally
+        // with a rule after a colon) give live cells a species, overrides the rule
+        // of a loaded pattern file, defaults to B3/S23
+        #[arg(long)]
+        pub rule: Option<Rule>,
+
+        // Edge topology: torus, plane, horizontal-cylinder, vertical-cylinder,
+        // klein-bottle or cross-surface, overrides the topology saved in a loaded
+        // pattern file, defaults to torus
+        #[arg(long)]
+        pub topology: Option<Topology>,
+    }
+
+    impl GameArgs {
+        pub fn soup(&self) -> Soup {
+            let soup = Soup::new(self.alive_probability).with_symmetry(self.symmetry);
+            let soup = match self.region {
+                Some(region) => soup.with_region(region),
+                None => soup,
+            };
+            match self.gradient {
+                Some(gradient) => soup.with_gradient(gradient, self.gradient_end),
+                None => soup,
+            }
+        }
     }
 }
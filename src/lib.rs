@@ -8,15 +8,36 @@ pub mod utils {
 }
 
 pub mod game {
+    use self::bitboard::BitBoard;
     use self::cell::{Action, Cell, Slot};
-    use crate::utils::add_mod_n;
-    use std::collections::HashMap;
+    use self::rule::{Grid, Neighborhood, Rule, RuleParseError};
+    use self::soup::{Soup, SoupError};
+    use self::topology::Topology;
+    use crate::apgcode::{self, ApgcodeError};
+    use crate::hashlife::HashLife;
+    use crate::objects::{self, Object};
+    use crate::period::{Cycle, PeriodDetector};
+    use crate::sparse::SparseUniverseError;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::VecDeque;
     use std::io::{BufRead, BufReader};
+    use std::path::{Path, PathBuf};
 
+    #[derive(Clone)]
     pub struct CellBoard {
         height: usize,
         width: usize,
-        cells: Vec<Vec<Cell>>,
+        storage: Storage,
+        topology: Topology,
+    }
+
+    // two-state boards are packed into u64 words by default, `Cells` is the
+    // general fallback that can hold any cell and be stepped by any rule
+    #[derive(Clone)]
+    enum Storage {
+        Cells(Vec<Vec<Cell>>),
+        Packed(BitBoard),
     }
 
     impl CellBoard {
@@ -24,13 +45,28 @@ pub mod game {
             Self {
                 height,
                 width,
-                cells: vec![vec![Cell::Dead; width]; height],
+                storage: Storage::Packed(BitBoard::new(height, width)),
+                topology: Topology::default(),
+            }
+        }
+
+        pub fn new_unpacked(height: usize, width: usize) -> Self {
+            Self {
+                height,
+                width,
+                storage: Storage::Cells(vec![vec![Cell::Dead; width]; height]),
+                topology: Topology::default(),
             }
         }
 
-        pub fn from_file(path: String) -> Result<Self, CellBoardCreationError> {
-            let file = std::fs::File::open(path)?;
-            let reader = BufReader::new(file);
+        pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CellBoardCreationError> {
+            let path = path.as_ref();
+            let file = std::fs::File::open(path)
+                .map_err(|error| CellBoardCreationError::from(error).in_file(path))?;
+            Self::from_reader(BufReader::new(file)).map_err(|error| error.in_file(path))
+        }
+
+        fn from_reader(reader: impl BufRead) -> Result<Self, CellBoardCreationError> {
             let mut row_length: Option<usize> = None;
             let mut row_vec = Vec::new();
             for (i, line_res) in reader.lines().enumerate() {
@@ -41,50 +77,223 @@ pub mod game {
                     None => row_length = Some(line_length),
                     Some(length) => {
                         if line_length != length {
-                            return Err(CellBoardCreationError::FormatError(
+                            return Err(CellBoardCreationError::from(
                                 FormatErrorVariant::RowLengthMismatch { row_index: i },
-                            ));
+                            )
+                            .at(i + 1, line_length.min(length) + 1));
                         } else if length == 0 {
-                            return Err(CellBoardCreationError::FormatError(
-                                FormatErrorVariant::EmptyRow,
-                            ));
+                            return Err(CellBoardCreationError::from(FormatErrorVariant::EmptyRow)
+                                .at(i + 1, 1));
                         }
                     }
                 }
-                for c in line.chars() {
+                for (j, c) in line.chars().enumerate() {
                     let cell = match c {
                         'X' => Cell::Alive,
                         'O' => Cell::Dead,
                         _ => {
-                            return Err(CellBoardCreationError::FormatError(
+                            return Err(CellBoardCreationError::from(
                                 FormatErrorVariant::UnrecognizedCharacter(c),
-                            ));
+                            )
+                            .at(i + 1, j + 1));
                         }
                     };
                     col_vec.push(cell);
                 }
                 row_vec.push(col_vec);
             }
-            Ok(Self {
+            let mut cell_board = Self {
                 height: row_vec.len(),
                 width: row_length.unwrap_or(0),
-                cells: row_vec,
-            })
+                storage: Storage::Cells(row_vec),
+                topology: Topology::default(),
+            };
+            cell_board.pack();
+            Ok(cell_board)
         }
 
         pub fn set_slot(&mut self, slot: Slot, cell: Cell) {
             let Slot(row, col) = slot;
-            self.cells[row][col] = cell;
+            // packed boards only hold plain live and dead cells
+            if let Cell::Dying(_) | Cell::Species(_) = cell {
+                self.unpack();
+            }
+            match &mut self.storage {
+                Storage::Cells(cells) => cells[row][col] = cell,
+                Storage::Packed(bit_board) => bit_board.set(row, col, matches!(cell, Cell::Alive)),
+            }
         }
 
         pub fn get_slot(&self, slot: Slot) -> Cell {
             let Slot(row, col) = slot;
-            self.cells[row][col]
+            match &self.storage {
+                Storage::Cells(cells) => cells[row][col],
+                Storage::Packed(bit_board) => {
+                    if bit_board.get(row, col) {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    }
+                }
+            }
         }
 
         pub fn apply_to_slot(&mut self, slot: Slot, action: Action) {
-            let Slot(row, col) = slot;
-            self.cells[row][col].apply(action);
+            let mut cell = self.get_slot(slot);
+            cell.apply(action);
+            self.set_slot(slot, cell);
+        }
+
+        pub fn clear(&mut self) {
+            match &mut self.storage {
+                Storage::Cells(cells) => cells
+                    .iter_mut()
+                    .flatten()
+                    .for_each(|cell| *cell = Cell::Dead),
+                Storage::Packed(bit_board) => *bit_board = BitBoard::new(self.height, self.width),
+            }
+        }
+
+        // the number of live cells in the neighborhood of every slot, row by row.
+        // Each row of a padded copy of the board, extended by `range` cells across
+        // the edges as the topology dictates, is turned into prefix sums so that a
+        // slot costs one subtraction per row of its neighborhood
+        pub fn neighborhood_counts(
+            &self,
+            neighborhood: Neighborhood,
+            range: usize,
+            middle: bool,
+        ) -> Vec<u32> {
+            if self.height == 0 || self.width == 0 {
+                return Vec::new();
+            }
+            let padded_height = self.height + 2 * range;
+            let prefix_width = self.width + 2 * range + 1;
+            let mut prefix_sums = vec![0u32; padded_height * prefix_width];
+            for padded_row in 0..padded_height {
+                let row_start = padded_row * prefix_width;
+                for padded_col in 0..prefix_width - 1 {
+                    let offset = (
+                        padded_row as i32 - range as i32,
+                        padded_col as i32 - range as i32,
+                    );
+                    let alive = self
+                        .neighbor(Slot(0, 0), offset)
+                        .is_some_and(|slot| self.get_slot(slot).is_alive());
+                    prefix_sums[row_start + padded_col + 1] =
+                        prefix_sums[row_start + padded_col] + alive as u32;
+                }
+            }
+            let half_widths: Vec<usize> = (0..=range)
+                .map(|dy| neighborhood.half_width(range, dy))
+                .collect();
+            let mut counts = Vec::with_capacity(self.height * self.width);
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    let mut count = 0;
+                    for padded_row in row..=row + 2 * range {
+                        let half_width = half_widths[padded_row.abs_diff(row + range)];
+                        let row_start = padded_row * prefix_width;
+                        count += prefix_sums[row_start + col + range + half_width + 1]
+                            - prefix_sums[row_start + col + range - half_width];
+                    }
+                    if !middle && self.get_slot(Slot(row, col)).is_alive() {
+                        count -= 1;
+                    }
+                    counts.push(count);
+                }
+            }
+            counts
+        }
+
+        pub fn slots_and_cells(&self) -> impl Iterator<Item = (Slot, Cell)> {
+            (0..self.height).flat_map(move |y| {
+                (0..self.width).map(move |x| {
+                    let slot = Slot(y, x);
+                    (slot, self.get_slot(slot))
+                })
+            })
+        }
+
+        pub fn live_slots(&self) -> impl Iterator<Item = Slot> {
+            (0..self.height).flat_map(move |row| {
+                (0..self.width)
+                    .map(move |col| Slot(row, col))
+                    .filter(|slot| self.get_slot(*slot).is_alive())
+            })
+        }
+
+        pub fn population(&self) -> usize {
+            match &self.storage {
+                Storage::Cells(cells) => cells
+                    .iter()
+                    .flatten()
+                    .filter(|cell| cell.is_alive())
+                    .count(),
+                Storage::Packed(bit_board) => bit_board.population(),
+            }
+        }
+
+        pub fn is_packed(&self) -> bool {
+            matches!(self.storage, Storage::Packed(_))
+        }
+
+        // boards with dying cells or cells of a species stay unpacked
+        pub fn pack(&mut self) {
+            if let Storage::Cells(cells) = &self.storage
+                && !cells
+                    .iter()
+                    .flatten()
+                    .any(|cell| matches!(cell, Cell::Dying(_) | Cell::Species(_)))
+            {
+                let mut bit_board = BitBoard::new(self.height, self.width);
+                for (row, cells) in cells.iter().enumerate() {
+                    for (col, cell) in cells.iter().enumerate() {
+                        bit_board.set(row, col, matches!(cell, Cell::Alive));
+                    }
+                }
+                self.storage = Storage::Packed(bit_board);
+            }
+        }
+
+        pub fn unpack(&mut self) {
+            if let Storage::Packed(_) = self.storage {
+                let cells = (0..self.height)
+                    .map(|row| {
+                        (0..self.width)
+                            .map(|col| self.get_slot(Slot(row, col)))
+                            .collect()
+                    })
+                    .collect();
+                self.storage = Storage::Cells(cells);
+            }
+        }
+
+        // returns None when the board is not packed, the caller then has to step
+        // it cell by cell
+        fn step_packed(&self, rule: &Rule) -> Option<Self> {
+            match &self.storage {
+                Storage::Packed(bit_board) => Some(Self {
+                    height: self.height,
+                    width: self.width,
+                    storage: Storage::Packed(bit_board.step(rule, self.topology)?),
+                    topology: self.topology,
+                }),
+                Storage::Cells(_) => None,
+            }
+        }
+
+        pub fn topology(&self) -> Topology {
+            self.topology
+        }
+
+        pub fn set_topology(&mut self, topology: Topology) {
+            self.topology = topology;
+        }
+
+        pub fn neighbor(&self, slot: Slot, offset: (i32, i32)) -> Option<Slot> {
+            self.topology
+                .neighbor(slot, offset, self.height, self.width)
         }
 
         pub fn height(&self) -> usize {
@@ -94,134 +303,724 @@ pub mod game {
         pub fn width(&self) -> usize {
             self.width
         }
+
+        // X/O grid as read by `from_file`, dying cells are written as dead and
+        // species are not kept
+        pub fn to_life(&self) -> String {
+            let mut life = String::new();
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    life.push(match self.get_slot(Slot(row, col)) {
+                        Cell::Alive | Cell::Species(_) => 'X',
+                        Cell::Dead | Cell::Dying(_) => 'O',
+                    });
+                }
+                life.push('\n');
+            }
+            life
+        }
+
+        pub fn from_rle(input: &str) -> Result<Self, CellBoardCreationError> {
+            Ok(rle::parse(input)?.cell_board)
+        }
+
+        pub fn to_rle(&self) -> String {
+            rle::write(self, Rule::default())
+        }
+
+        pub fn from_plaintext(input: &str) -> Result<Self, CellBoardCreationError> {
+            Ok(plaintext::parse(input)?.cell_board)
+        }
+
+        pub fn to_plaintext(&self) -> String {
+            plaintext::write(self, None)
+        }
+
+        pub fn from_life105(input: &str) -> Result<Self, CellBoardCreationError> {
+            Ok(life105::parse(input)?.cell_board)
+        }
+
+        pub fn to_life105(&self) -> String {
+            life105::write(self, Rule::default())
+        }
+
+        pub fn from_macrocell(input: &str) -> Result<Self, CellBoardCreationError> {
+            Self::from_hash_life(&HashLife::from_macrocell(input)?)
+        }
+
+        // the bounding box of the universe, refused if it is too large to hold
+        pub fn from_hash_life(hash_life: &HashLife) -> Result<Self, CellBoardCreationError> {
+            let Some(bounding_box) = hash_life.bounding_box() else {
+                return Ok(Self::new(0, 0));
+            };
+            let (height, width) = (bounding_box.height(), bounding_box.width());
+            check_board_size(height, width)?;
+            let mut cell_board = Self::new(height as usize, width as usize);
+            for (row, col) in hash_life.live_cells() {
+                cell_board.set_slot(
+                    Slot(
+                        (row - bounding_box.top) as usize,
+                        (col - bounding_box.left) as usize,
+                    ),
+                    Cell::Alive,
+                );
+            }
+            Ok(cell_board)
+        }
+
+        pub fn to_macrocell(&self) -> String {
+            HashLife::from_cell_board(self, Rule::default())
+                .expect("B3/S23 is supported by HashLife")
+                .to_macrocell()
+        }
+
+        pub fn from_life106(input: &str) -> Result<Self, CellBoardCreationError> {
+            Ok(life106::parse(input)?.cell_board)
+        }
+
+        pub fn to_life106(&self) -> String {
+            life106::write(self)
+        }
     }
 
-    impl ToString for CellBoard {
-        fn to_string(&self) -> String {
-            let mut string_representation = String::new();
-            for row in &self.cells {
-                for cell in row {
-                    string_representation += match cell {
-                        Cell::Dead => " ",
-                        Cell::Alive => "X",
+    impl std::fmt::Display for CellBoard {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    match self.get_slot(Slot(row, col)) {
+                        Cell::Dead => write!(f, " ")?,
+                        Cell::Alive => write!(f, "X")?,
+                        Cell::Dying(_) => write!(f, ".")?,
+                        Cell::Species(species) => write!(f, "{}", species)?,
                     }
                 }
-                string_representation += "\n";
+                writeln!(f)?;
             }
-            string_representation
+            Ok(())
         }
     }
     pub struct Game {
         generation: usize,
         cell_board: CellBoard,
+        rule: Rule,
+        period_detector: Option<PeriodDetector>,
+        history: Option<History>,
+    }
+
+    // boards with their generation, edits keep the generation of the board they
+    // were made on
+    struct History {
+        capacity: usize,
+        past: VecDeque<(usize, CellBoard)>,
+        // boards that were stepped back over, the next one is last
+        future: Vec<(usize, CellBoard)>,
     }
 
+    #[derive(Debug)]
     pub enum FormatErrorVariant {
         RowLengthMismatch { row_index: usize },
         UnrecognizedCharacter(char),
         EmptyRow,
+        InvalidHeader(String),
+        InvalidCoordinates(String),
+        InvalidRule(RuleParseError),
+        UnsupportedRule(Box<Rule>),
+        InvalidNode(String),
+        PatternExceedsSize { height: usize, width: usize },
+        PatternTooLarge { height: u64, width: u64 },
+        RunCountOutOfRange,
+        StateOutOfRange(usize),
+    }
+
+    impl std::fmt::Display for FormatErrorVariant {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::RowLengthMismatch { row_index } => write!(
+                    f,
+                    "row at index {} does not match previous row lengths",
+                    row_index
+                ),
+                Self::UnrecognizedCharacter(c) => write!(f, "unrecognized character '{}'", c),
+                Self::EmptyRow => write!(f, "empty rows are not allowed"),
+                Self::InvalidHeader(header) => write!(f, "invalid header: {}", header),
+                Self::InvalidCoordinates(line) => write!(f, "invalid cell coordinates: {}", line),
+                Self::InvalidRule(error) => write!(f, "invalid rule: {}", error),
+                Self::UnsupportedRule(rule) => {
+                    write!(f, "rule {} is not supported for this pattern format", rule)
+                }
+                Self::InvalidNode(line) => write!(f, "invalid macrocell node: {}", line),
+                Self::PatternExceedsSize { height, width } => write!(
+                    f,
+                    "pattern does not fit into the declared size of {}x{}",
+                    width, height
+                ),
+                Self::PatternTooLarge { height, width } => write!(
+                    f,
+                    "pattern of {}x{} cells is too large to fit into a board",
+                    width, height
+                ),
+                Self::RunCountOutOfRange => write!(f, "run count is out of range"),
+                Self::StateOutOfRange(state) => {
+                    write!(f, "cell state {} is out of range for the rule", state)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for FormatErrorVariant {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Self::InvalidRule(error) => Some(error),
+                _ => None,
+            }
+        }
+    }
+
+    // 1-based line and column of the input an error was found at
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Position {
+        pub line: usize,
+        pub column: usize,
     }
 
+    #[derive(Debug)]
     pub enum CellBoardCreationError {
-        FileError,
-        FormatError(FormatErrorVariant),
+        FileError {
+            path: Option<PathBuf>,
+            source: std::io::Error,
+        },
+        FormatError {
+            path: Option<PathBuf>,
+            position: Option<Position>,
+            variant: FormatErrorVariant,
+        },
+    }
+
+    impl CellBoardCreationError {
+        // records where in the input a format error was found, keeping a position
+        // that was recorded before
+        pub fn at(mut self, line: usize, column: usize) -> Self {
+            if let Self::FormatError { position, .. } = &mut self {
+                position.get_or_insert(Position { line, column });
+            }
+            self
+        }
+
+        pub fn in_file(mut self, file_path: impl AsRef<Path>) -> Self {
+            match &mut self {
+                Self::FileError { path, .. } | Self::FormatError { path, .. } => {
+                    *path = Some(file_path.as_ref().to_owned())
+                }
+            }
+            self
+        }
+
+        pub fn path(&self) -> Option<&Path> {
+            match self {
+                Self::FileError { path, .. } | Self::FormatError { path, .. } => path.as_deref(),
+            }
+        }
+
+        pub fn position(&self) -> Option<Position> {
+            match self {
+                Self::FileError { .. } => None,
+                Self::FormatError { position, .. } => *position,
+            }
+        }
+    }
+
+    impl std::fmt::Display for CellBoardCreationError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::FileError { path, source } => match path {
+                    Some(path) => write!(f, "could not read '{}': {}", path.display(), source),
+                    None => write!(f, "could not read pattern: {}", source),
+                },
+                Self::FormatError {
+                    path,
+                    position,
+                    variant,
+                } => {
+                    if let Some(path) = path {
+                        write!(f, "{}:", path.display())?;
+                    }
+                    if let Some(Position { line, column }) = position {
+                        write!(f, "{}:{}:", line, column)?;
+                    }
+                    if path.is_some() || position.is_some() {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", variant)
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for CellBoardCreationError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Self::FileError { source, .. } => Some(source),
+                Self::FormatError { variant, .. } => variant.source(),
+            }
+        }
     }
 
     impl From<std::io::Error> for CellBoardCreationError {
-        fn from(_: std::io::Error) -> Self {
-            Self::FileError
+        fn from(source: std::io::Error) -> Self {
+            Self::FileError { path: None, source }
+        }
+    }
+
+    impl From<FormatErrorVariant> for CellBoardCreationError {
+        fn from(variant: FormatErrorVariant) -> Self {
+            Self::FormatError {
+                path: None,
+                position: None,
+                variant,
+            }
         }
     }
 
     impl Game {
         pub fn new(height: usize, width: usize) -> Self {
-            Self {
-                generation: 0,
-                cell_board: CellBoard::new(height, width),
+            Self::from_cell_board(CellBoard::new(height, width))
+        }
+        pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CellBoardCreationError> {
+            let path = path.as_ref();
+            Self::read_pattern_file(path).map_err(|error| error.in_file(path))
+        }
+        fn read_pattern_file(path: &Path) -> Result<Self, CellBoardCreationError> {
+            let content = std::fs::read_to_string(path)?;
+            let format =
+                PatternFormat::from_path(path).unwrap_or_else(|| PatternFormat::detect(&content));
+            match format {
+                PatternFormat::Life => Ok(Self::from_cell_board(CellBoard::from_reader(
+                    content.as_bytes(),
+                )?)),
+                PatternFormat::Rle => Self::from_rle(&content),
+                PatternFormat::Plaintext => Ok(Self::from_pattern(plaintext::parse(&content)?)),
+                PatternFormat::Life105 => Ok(Self::from_pattern(life105::parse(&content)?)),
+                PatternFormat::Life106 => Ok(Self::from_pattern(life106::parse(&content)?)),
+                PatternFormat::Macrocell => {
+                    let hash_life = HashLife::from_macrocell(&content)?;
+                    let comments = content
+                        .lines()
+                        .filter_map(|line| line.trim().strip_prefix("#C"))
+                        .map(|comment| comment.trim().to_owned())
+                        .collect();
+                    let mut game = Self::from_pattern(Pattern {
+                        cell_board: CellBoard::from_hash_life(&hash_life)?,
+                        rule: Some(hash_life.rule()),
+                        name: None,
+                        comments,
+                    });
+                    game.generation = hash_life.generation() as usize;
+                    Ok(game)
+                }
             }
         }
-        pub fn from_file(path: String) -> Result<Self, CellBoardCreationError> {
-            let cell_board = CellBoard::from_file(path)?;
-            Ok(Self {
+        pub fn from_rle(input: &str) -> Result<Self, CellBoardCreationError> {
+            Ok(Self::from_pattern(rle::parse(input)?))
+        }
+        // `key: value` comments written by `to_pattern` after the metadata marker
+        // restore the generation and topology, the rule for formats without a rule
+        // of their own and the board size for formats that only keep the live
+        // cells; other comments are left alone
+        pub fn from_pattern(pattern: Pattern) -> Self {
+            let mut game = Self::from_cell_board(pattern.cell_board)
+                .with_rule(pattern.rule.unwrap_or_default());
+            let mut size = None;
+            let mut offset = (0, 0);
+            for comment in &pattern.comments {
+                let Some((key, value)) = comment
+                    .strip_prefix(METADATA_MARKER)
+                    .and_then(|metadata| metadata.split_once(':'))
+                else {
+                    continue;
+                };
+                let value = value.trim();
+                match key.trim() {
+                    "generation" => {
+                        if let Ok(generation) = value.parse() {
+                            game.generation = generation;
+                        }
+                    }
+                    "topology" => {
+                        if let Ok(topology) = value.parse() {
+                            game = game.with_topology(topology);
+                        }
+                    }
+                    "rule" if pattern.rule.is_none() => {
+                        if let Ok(rule) = value.parse() {
+                            game = game.with_rule(rule);
+                        }
+                    }
+                    "size" => size = parse_pair(value, 'x').or(size),
+                    "offset" => offset = parse_pair(value, ',').unwrap_or(offset),
+                    _ => {}
+                }
+            }
+            if let Some((height, width)) = size {
+                game.restore_size(height, width, offset);
+            }
+            game
+        }
+        // moves the board read from a pattern to `offset` on a board of the saved
+        // size, unless it does not fit there
+        fn restore_size(&mut self, height: usize, width: usize, (top, left): (usize, usize)) {
+            let fits = |start: usize, length: usize, size: usize| {
+                start.checked_add(length).is_some_and(|end| end <= size)
+            };
+            if !fits(top, self.height(), height)
+                || !fits(left, self.width(), width)
+                || check_board_size(height as u64, width as u64).is_err()
+            {
+                return;
+            }
+            let mut cell_board = CellBoard::new(height, width);
+            cell_board.set_topology(self.topology());
+            for (Slot(row, col), cell) in self.cell_board.slots_and_cells() {
+                if cell != Cell::Dead {
+                    cell_board.set_slot(Slot(top + row, left + col), cell);
+                }
+            }
+            self.cell_board = cell_board;
+            self.board_changed();
+        }
+        pub fn from_cell_board(cell_board: CellBoard) -> Self {
+            Self {
                 generation: 0,
                 cell_board,
-            })
+                rule: Rule::default(),
+                period_detector: None,
+                history: None,
+            }
         }
-        pub fn randomize(mut self, alive_probability: f64) -> Self {
-            for row in 0..self.cell_board.height() {
-                for col in 0..self.cell_board.width() {
-                    self.cell_board.set_slot(
-                        Slot(row, col),
-                        if rand::random_bool(alive_probability) {
-                            Cell::Alive
-                        } else {
-                            Cell::Dead
-                        },
+        pub fn to_rle(&self) -> String {
+            rle::write(&self.cell_board, self.rule)
+        }
+        pub fn to_life(&self) -> String {
+            self.cell_board.to_life()
+        }
+        // the apgcode of the board's live cells on an unbounded plane
+        pub fn apgcode(&self) -> Result<String, ApgcodeError> {
+            apgcode::encode(&self.cell_board, self.rule)
+        }
+        // the live cells split into classified objects as if the board were an
+        // unbounded plane, so objects across a wrapping edge come out in pieces
+        pub fn objects(&self) -> Result<Vec<Object>, SparseUniverseError> {
+            let live_cells = self
+                .cell_board
+                .slots_and_cells()
+                .filter(|(_, cell)| cell.is_alive())
+                .map(|(Slot(row, col), _)| (row as i64, col as i64));
+            objects::separate(self.rule, live_cells)
+        }
+        // the board in the given format with the generation, rule and topology kept
+        // in comments where the format has no place for them, None if the format
+        // cannot represent the game
+        pub fn to_pattern(&self, format: PatternFormat) -> Option<String> {
+            let generation = format!("{} generation: {}", METADATA_MARKER, self.generation);
+            let topology = format!("{} topology: {}", METADATA_MARKER, self.topology());
+            let rule = format!("{} rule: {}", METADATA_MARKER, self.rule);
+            // formats that only keep the live cells read back their bounding box,
+            // which is put back at its offset on a board of this size
+            let size = format!(
+                "{} size: {}x{}",
+                METADATA_MARKER,
+                self.height(),
+                self.width()
+            );
+            let top = self
+                .cell_board
+                .live_slots()
+                .next()
+                .map_or(0, |Slot(row, _)| row);
+            let left = self
+                .cell_board
+                .live_slots()
+                .map(|Slot(_, col)| col)
+                .min()
+                .unwrap_or(0);
+            let offset = format!("{} offset: {},{}", METADATA_MARKER, top, left);
+            let comment_lines = |prefix: &str, comments: &[&String]| -> String {
+                comments
+                    .iter()
+                    .map(|comment| format!("{}{}\n", prefix, comment))
+                    .collect()
+            };
+            Some(match format {
+                PatternFormat::Life => self.to_life(),
+                PatternFormat::Rle => {
+                    comment_lines("#C ", &[&generation, &topology]) + &self.to_rle()
+                }
+                PatternFormat::Plaintext => {
+                    comment_lines("!", &[&generation, &rule, &topology])
+                        + &plaintext::write(&self.cell_board, None)
+                }
+                PatternFormat::Life105 => insert_after_header(
+                    life105::write(&self.cell_board, self.rule),
+                    comment_lines("#D ", &[&generation, &topology, &size, &offset]),
+                ),
+                PatternFormat::Life106 => insert_after_header(
+                    life106::write(&self.cell_board),
+                    comment_lines("#D ", &[&generation, &rule, &topology, &size, &offset]),
+                ),
+                PatternFormat::Macrocell => {
+                    let mut hash_life =
+                        HashLife::from_cell_board(&self.cell_board, self.rule).ok()?;
+                    hash_life.set_generation(self.generation as u64);
+                    insert_after_header(
+                        hash_life.to_macrocell(),
+                        comment_lines("#C ", &[&topology, &size, &offset]),
                     )
                 }
-            }
+            })
+        }
+        pub fn with_rule(mut self, rule: Rule) -> Self {
+            self.rule = rule;
+            self.board_changed();
             self
         }
-        pub fn tick(&mut self) {
-            let mut actions_to_apply = HashMap::new();
-            for row in 0..self.cell_board.height() {
-                for col in 0..self.cell_board.width() {
-                    let slot = Slot(row, col);
-                    let action = self.get_action(slot);
-                    actions_to_apply.insert(slot, action);
+        pub fn with_topology(mut self, topology: Topology) -> Self {
+            self.cell_board.set_topology(topology);
+            self.board_changed();
+            self
+        }
+        // remembers a hash of every generation from now on so that `cycle` can
+        // report when the board returns to an earlier state
+        pub fn with_period_detection(mut self) -> Self {
+            self.period_detector = Some(PeriodDetector::default());
+            self.observe_period();
+            self
+        }
+        // keeps at most `capacity` previous boards so that `step_back` can rewind
+        pub fn with_history(mut self, capacity: usize) -> Self {
+            self.history = Some(History {
+                capacity,
+                past: VecDeque::with_capacity(capacity),
+                future: Vec::new(),
+            });
+            self
+        }
+        // called whenever the board is modified other than by ticking
+        fn board_changed(&mut self) {
+            if let Some(history) = &mut self.history {
+                history.future.clear();
+            }
+            if let Some(period_detector) = &mut self.period_detector {
+                period_detector.reset();
+                self.observe_period();
+            }
+        }
+        // keeps the current board so that `step_back` can return to it
+        fn remember(&mut self) {
+            if let Some(history) = &mut self.history
+                && history.capacity > 0
+            {
+                if history.past.len() == history.capacity {
+                    history.past.pop_front();
+                }
+                history
+                    .past
+                    .push_back((self.generation, self.cell_board.clone()));
+            }
+        }
+        // undoes the last tick or edit
+        pub fn step_back(&mut self) -> bool {
+            let Some(history) = &mut self.history else {
+                return false;
+            };
+            let Some((generation, previous)) = history.past.pop_back() else {
+                return false;
+            };
+            let current = std::mem::replace(&mut self.cell_board, previous);
+            history.future.push((self.generation, current));
+            self.generation = generation;
+            if let Some(period_detector) = &mut self.period_detector {
+                period_detector.rewind(generation as u64);
+            }
+            true
+        }
+        pub fn history_len(&self) -> usize {
+            self.history
+                .as_ref()
+                .map_or(0, |history| history.past.len())
+        }
+        fn observe_period(&mut self) {
+            if let Some(period_detector) = &mut self.period_detector {
+                if self.rule.states() == 2 && self.rule.species() == 1 {
+                    let live_cells = self
+                        .cell_board
+                        .live_slots()
+                        .map(|Slot(row, col)| (row as i64, col as i64));
+                    period_detector.observe(self.generation as u64, live_cells);
+                } else {
+                    // the dying cells decide what is born next and the species what it
+                    // is born as, so they are part of the state
+                    let cells = self
+                        .cell_board
+                        .slots_and_cells()
+                        .filter(|(_, cell)| *cell != Cell::Dead)
+                        .map(|(Slot(row, col), cell)| {
+                            let state = cell.species().map_or(cell, Cell::Species);
+                            ((row as i64, col as i64), state)
+                        });
+                    period_detector.observe_states(self.generation as u64, cells);
                 }
             }
-            for (slot, action) in actions_to_apply {
-                self.cell_board.apply_to_slot(slot, action);
+        }
+        pub fn cycle(&self) -> Option<Cycle> {
+            self.period_detector.as_ref()?.cycle()
+        }
+        pub fn randomize(self, alive_probability: f64) -> Self {
+            self.randomize_with(alive_probability, &mut rand::rng())
+        }
+        // the same seed gives the same soup on every platform
+        pub fn randomize_seeded(self, alive_probability: f64, seed: u64) -> Self {
+            self.randomize_with(alive_probability, &mut ChaCha8Rng::seed_from_u64(seed))
+        }
+        pub fn randomize_soup_seeded(self, soup: &Soup, seed: u64) -> Result<Self, SoupError> {
+            self.randomize_soup(soup, &mut ChaCha8Rng::seed_from_u64(seed))
+        }
+        pub fn randomize_with(self, alive_probability: f64, rng: &mut impl Rng) -> Self {
+            self.randomize_soup(&Soup::new(alive_probability), rng)
+                .expect("a soup without a region fits any board")
+        }
+        // live cells of a multi-color rule are of a random species
+        pub fn randomize_soup(
+            mut self,
+            soup: &Soup,
+            rng: &mut impl Rng,
+        ) -> Result<Self, SoupError> {
+            self.remember();
+            soup.fill(&mut self.cell_board, self.rule.species(), rng)?;
+            self.board_changed();
+            Ok(self)
+        }
+        pub fn tick(&mut self) {
+            self.remember();
+            // boards that were stepped back over are replayed as they were
+            if let Some((generation, next)) = self
+                .history
+                .as_mut()
+                .and_then(|history| history.future.pop())
+            {
+                self.cell_board = next;
+                self.generation = generation;
+                self.observe_period();
+                return;
+            }
+            match self.cell_board.step_packed(&self.rule) {
+                Some(cell_board) => self.cell_board = cell_board,
+                None => {
+                    let neighborhood_counts = self.rule.is_larger_than_life().then(|| {
+                        self.cell_board.neighborhood_counts(
+                            self.rule.neighborhood(),
+                            self.rule.range(),
+                            self.rule.includes_middle(),
+                        )
+                    });
+                    let actions_to_apply: Vec<(Slot, Action)> = self
+                        .slots_and_cells()
+                        .enumerate()
+                        .map(|(index, (slot, cell))| {
+                            let action = match (&neighborhood_counts, self.grid()) {
+                                (Some(counts), _) => self.rule.action(cell, counts[index] as usize),
+                                (None, Grid::Square) => self.rule.configuration_action(
+                                    cell,
+                                    self.neighbor_configuration(slot) as u8,
+                                ),
+                                (None, _) => self.rule.action(
+                                    cell,
+                                    self.neighbor_configuration(slot).count_ones() as usize,
+                                ),
+                            };
+                            match action {
+                                Action::Live if !cell.is_alive() && self.rule.species() > 1 => {
+                                    (slot, Action::Spawn(self.newborn_species(slot)))
+                                }
+                                _ => (slot, action),
+                            }
+                        })
+                        .collect();
+                    for (slot, action) in actions_to_apply {
+                        self.cell_board.apply_to_slot(slot, action);
+                    }
+                }
             }
             self.generation += 1;
+            self.observe_period();
         }
 
-        fn get_action(&self, slot: Slot) -> Action {
-            let Slot(row, col) = slot;
-            let mut live_neighbors: usize = 0;
-            for (dy, dx) in [
-                (0, 1),
-                (-1_i32, 1),
-                (-1, 0),
-                (-1, -1_i32),
-                (0, -1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ] {
-                let (new_y, new_x) = (
-                    add_mod_n(row, dy, self.cell_board.height()),
-                    add_mod_n(col, dx, self.cell_board.width()),
-                );
-                if let Cell::Alive = self.cell_board.get_slot(Slot(new_y, new_x)) {
-                    live_neighbors += 1;
+        // the live cells among the neighbors of `slot` on the grid of the rule, as
+        // bits in the order of `Grid::neighbor_offsets`
+        fn neighbor_configuration(&self, slot: Slot) -> u16 {
+            let mut configuration = 0;
+            for (bit, offset) in self
+                .grid()
+                .neighbor_offsets(slot.0, slot.1)
+                .iter()
+                .enumerate()
+            {
+                if let Some(neighbor) = self.cell_board.neighbor(slot, *offset)
+                    && self.cell_board.get_slot(neighbor).is_alive()
+                {
+                    configuration |= 1 << bit;
                 }
             }
-            match self.cell_board.get_slot(slot) {
-                Cell::Alive => match live_neighbors {
-                    2..=3 => Action::Live,
-                    0..=1 | 4.. => Action::Die,
-                },
-                Cell::Dead => match live_neighbors {
-                    3 => Action::Live,
-                    _ => Action::Die,
-                },
+            configuration
+        }
+        // the species most of the live neighbors of `slot` belong to; when all but
+        // one species are tied, as for three parents of different colors in
+        // QuadLife, the missing one, otherwise the lowest numbered of the tied
+        fn newborn_species(&self, slot: Slot) -> u8 {
+            let mut parents = vec![0; self.rule.species() as usize];
+            for offset in self.grid().neighbor_offsets(slot.0, slot.1) {
+                if let Some(neighbor) = self.cell_board.neighbor(slot, *offset)
+                    && let Some(species) = self.cell_board.get_slot(neighbor).species()
+                    && let Some(count) = parents.get_mut(species as usize - 1)
+                {
+                    *count += 1;
+                }
             }
+            let most = parents.iter().copied().max().unwrap_or(0);
+            let tied = parents.iter().filter(|count| **count == most).count();
+            let missing: Vec<usize> = (0..parents.len()).filter(|i| parents[*i] == 0).collect();
+            let species = match missing[..] {
+                [missing] if tied > 1 && tied == parents.len() - 1 => missing,
+                _ => parents.iter().position(|count| *count == most).unwrap_or(0),
+            };
+            species as u8 + 1
         }
         pub fn apply_action(&mut self, slot: Slot, action: Action) {
+            self.remember();
             self.cell_board.apply_to_slot(slot, action);
+            self.board_changed();
+        }
+        pub fn get_slot(&self, slot: Slot) -> Cell {
+            self.cell_board.get_slot(slot)
+        }
+        pub fn clear(&mut self) {
+            self.remember();
+            self.cell_board.clear();
+            self.board_changed();
         }
         pub fn generation(&self) -> usize {
             self.generation
         }
+        pub fn rule(&self) -> Rule {
+            self.rule
+        }
+        pub fn topology(&self) -> Topology {
+            self.cell_board.topology()
+        }
+        // the grid whose neighbors the rule counts
+        pub fn grid(&self) -> Grid {
+            self.rule.grid()
+        }
         pub fn slots_and_cells(&self) -> impl Iterator<Item = (Slot, Cell)> {
-            (0..self.cell_board.height()).flat_map(move |y| {
-                (0..self.cell_board.width()).map(move |x| {
-                    let slot = Slot(y, x);
-                    (slot, self.cell_board.get_slot(slot))
-                })
-            })
+            self.cell_board.slots_and_cells()
         }
         pub fn height(&self) -> usize {
             self.cell_board.height()
@@ -229,6 +1028,142 @@ pub mod game {
         pub fn width(&self) -> usize {
             self.cell_board.width()
         }
+        pub fn population(&self) -> usize {
+            self.cell_board.population()
+        }
+        // the live cells of each species of the rule, species 1 first
+        pub fn species_population(&self) -> Vec<usize> {
+            let mut population = vec![0; self.rule.species() as usize];
+            for (_, cell) in self.slots_and_cells() {
+                if let Some(species) = cell.species()
+                    && let Some(count) = population.get_mut(species as usize - 1)
+                {
+                    *count += 1;
+                }
+            }
+            population
+        }
+    }
+
+    // "20x30" or "4,5" split at the separator
+    fn parse_pair(value: &str, separator: char) -> Option<(usize, usize)> {
+        let (first, second) = value.split_once(separator)?;
+        Some((first.trim().parse().ok()?, second.trim().parse().ok()?))
+    }
+
+    fn insert_after_header(pattern: String, lines: String) -> String {
+        match pattern.split_once('\n') {
+            Some((header, body)) => format!("{}\n{}{}", header, lines, body),
+            None => pattern + "\n" + &lines,
+        }
+    }
+
+    // starts the comments `to_pattern` keeps the game's metadata in, so that
+    // comments written by people or other programs are not mistaken for it
+    const METADATA_MARKER: &str = "[game-of-life]";
+
+    // boards are allocated in full, so larger patterns have to stay in a
+    // `HashLife` or `SparseUniverse`
+    const MAX_CELL_BOARD_CELLS: u64 = 1 << 26;
+
+    pub fn check_board_size(height: u64, width: u64) -> Result<(), CellBoardCreationError> {
+        if height.saturating_mul(width) > MAX_CELL_BOARD_CELLS {
+            return Err(CellBoardCreationError::from(
+                FormatErrorVariant::PatternTooLarge { height, width },
+            ));
+        }
+        Ok(())
+    }
+
+    // a board read from a pattern file together with its metadata
+    pub struct Pattern {
+        pub cell_board: CellBoard,
+        pub rule: Option<Rule>,
+        pub name: Option<String>,
+        pub comments: Vec<String>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PatternFormat {
+        // X/O grid
+        Life,
+        Rle,
+        // LifeWiki .cells
+        Plaintext,
+        Life105,
+        Life106,
+        Macrocell,
+    }
+
+    impl PatternFormat {
+        // .life and .lif are shared by the X/O grid and the Life 1.0x formats, so
+        // those have to be told apart by `detect`
+        pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+            let extension = path.as_ref().extension()?.to_str()?;
+            match extension.to_ascii_lowercase().as_str() {
+                "rle" => Some(Self::Rle),
+                "cells" => Some(Self::Plaintext),
+                "mc" => Some(Self::Macrocell),
+                _ => None,
+            }
+        }
+
+        // X/O grids only ever contain those two letters, so anything with a comment
+        // line or an `x = ` header is treated as RLE, and grids of `.` and `O` as
+        // plaintext
+        pub fn detect(content: &str) -> Self {
+            let mut lines = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty());
+            match lines.next() {
+                Some(line) if line.starts_with("#Life 1.05") => Self::Life105,
+                Some(line) if line.starts_with("#Life 1.06") => Self::Life106,
+                Some(line) if line.starts_with("[M2]") => Self::Macrocell,
+                Some(line) if line.starts_with('#') => Self::Rle,
+                Some(line) if line.starts_with('x') && line.contains('=') => Self::Rle,
+                Some(line) if line.starts_with('!') => Self::Plaintext,
+                Some(line)
+                    if std::iter::once(line)
+                        .chain(lines)
+                        .all(|line| line.chars().all(|c| c == '.' || c == 'O')) =>
+                {
+                    if content.contains('.') {
+                        Self::Plaintext
+                    } else {
+                        Self::Life
+                    }
+                }
+                _ => Self::Life,
+            }
+        }
+    }
+
+    // the smallest board holding all of the given (row, col) positions, refused
+    // if it is too large to hold
+    fn cell_board_from_live_cells(
+        live_cells: &[(i64, i64)],
+    ) -> Result<CellBoard, CellBoardCreationError> {
+        let top = live_cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+        let left = live_cells.iter().map(|(_, col)| *col).min().unwrap_or(0);
+        let bottom = live_cells.iter().map(|(row, _)| *row).max().unwrap_or(-1);
+        let right = live_cells.iter().map(|(_, col)| *col).max().unwrap_or(-1);
+        // cells from first to last, or u64::MAX if that does not even fit an i64
+        let extent = |first: i64, last: i64| {
+            last.checked_sub(first)
+                .and_then(|distance| distance.checked_add(1))
+                .map_or(u64::MAX, |extent| extent as u64)
+        };
+        let (height, width) = (extent(top, bottom), extent(left, right));
+        check_board_size(height, width)?;
+        let mut cell_board = CellBoard::new(height as usize, width as usize);
+        for (row, col) in live_cells {
+            cell_board.set_slot(
+                Slot((row - top) as usize, (col - left) as usize),
+                Cell::Alive,
+            );
+        }
+        Ok(cell_board)
     }
 
     const DEFAULT_GAME_SIZE: usize = 8;
@@ -239,28 +1174,47 @@ pub mod game {
         }
     }
 
-    impl ToString for Game {
-        fn to_string(&self) -> String {
-            let mut string_representation = String::new();
-            string_representation += &self.cell_board.to_string();
-            string_representation += "\n";
-            string_representation += &format!("Generation: {}", self.generation);
-            string_representation
+    impl std::fmt::Display for Game {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "{}", self.cell_board)?;
+            write!(f, "Generation: {}", self.generation)
         }
     }
 
     pub mod cell {
-        #[derive(Clone, Copy)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
         pub enum Cell {
             Dead,
             Alive,
+            // refractory state of a Generations rule, counting the generations since
+            // the cell was alive starting at 1; it is neither alive nor can it be born
+            Dying(u8),
+            // live cell of a multi-color rule, with the species numbered from 1; a
+            // plain live cell belongs to species 1
+            Species(u8),
         }
 
         impl Cell {
+            // a live cell stays the species it is
             pub fn apply(&mut self, action: Action) {
                 match action {
                     Action::Die => *self = Cell::Dead,
+                    Action::Live if self.is_alive() => {}
                     Action::Live => *self = Cell::Alive,
+                    Action::Decay(age) => *self = Cell::Dying(age),
+                    Action::Spawn(species) => *self = Cell::Species(species),
+                }
+            }
+
+            pub fn is_alive(&self) -> bool {
+                self.species().is_some()
+            }
+
+            pub fn species(&self) -> Option<u8> {
+                match self {
+                    Cell::Alive => Some(1),
+                    Cell::Species(species) => Some(*species),
+                    Cell::Dead | Cell::Dying(_) => None,
                 }
             }
         }
@@ -269,9 +1223,4141 @@ pub mod game {
         pub enum Action {
             Die,
             Live,
+            Decay(u8),
+            // born as a cell of the given species
+            Spawn(u8),
         }
 
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub struct Slot(pub usize, pub usize);
     }
+
+    pub mod rule {
+        use super::cell::{Action, Cell};
+        use super::topology::Topology;
+        use std::ops::RangeInclusive;
+        use std::str::FromStr;
+
+        const MAX_NEIGHBORS: usize = 8;
+        // the neighbors of a triangle on the triangular grid
+        const MAX_GRID_NEIGHBORS: usize = 12;
+        // largest Larger than Life range, as in Golly
+        const MAX_RANGE: usize = 500;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum Neighborhood {
+            // the square of cells at most `range` rows and columns away
+            #[default]
+            Moore,
+            // the diamond of cells at most `range` steps along rows and columns away
+            VonNeumann,
+            // the cells closer than `range + 1/2` to the center
+            Circular,
+        }
+
+        impl Neighborhood {
+            // how many columns the neighborhood extends to either side in the row
+            // `dy` rows away from the center
+            pub fn half_width(&self, range: usize, dy: usize) -> usize {
+                match self {
+                    Neighborhood::Moore => range,
+                    Neighborhood::VonNeumann => range - dy,
+                    Neighborhood::Circular => (range * range + range - dy * dy).isqrt(),
+                }
+            }
+
+            // the cells of the neighborhood other than the middle
+            pub fn size(&self, range: usize) -> usize {
+                let row = |dy: usize| 2 * self.half_width(range, dy) + 1;
+                row(0) + 2 * (1..=range).map(row).sum::<usize>() - 1
+            }
+
+            fn letter(&self) -> char {
+                match self {
+                    Neighborhood::Moore => 'M',
+                    Neighborhood::VonNeumann => 'N',
+                    Neighborhood::Circular => 'C',
+                }
+            }
+        }
+
+        // the shape of the cells, which decides which cells are neighbors
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum Grid {
+            #[default]
+            Square,
+            // hexagons in rows, every row shifted left by half a cell from the
+            // row above
+            Hexagonal,
+            // triangles pointing up where row + column is even and down elsewhere
+            Triangular,
+        }
+
+        impl Grid {
+            pub fn neighbors(&self) -> usize {
+                match self {
+                    Grid::Square => MAX_NEIGHBORS,
+                    Grid::Hexagonal => 6,
+                    Grid::Triangular => MAX_GRID_NEIGHBORS,
+                }
+            }
+
+            // the (dy, dx) offsets of the neighbors of the cell at `row` and `col`,
+            // on the square grid in the order N, NE, E, SE, S, SW, W, NW
+            pub fn neighbor_offsets(&self, row: usize, col: usize) -> &'static [(i32, i32)] {
+                match self {
+                    Grid::Square => &[
+                        (-1, 0),
+                        (-1, 1),
+                        (0, 1),
+                        (1, 1),
+                        (1, 0),
+                        (1, -1),
+                        (0, -1),
+                        (-1, -1),
+                    ],
+                    // the square neighbors but NE and SW, as hexagonal boards are
+                    // stored skewed in Golly
+                    Grid::Hexagonal => &[(-1, -1), (-1, 0), (0, 1), (1, 1), (1, 0), (0, -1)],
+                    // every triangle sharing an edge or a corner: three above, four
+                    // beside and five below a triangle pointing up
+                    Grid::Triangular if (row + col).is_multiple_of(2) => &[
+                        (-1, -1),
+                        (-1, 0),
+                        (-1, 1),
+                        (0, -2),
+                        (0, -1),
+                        (0, 1),
+                        (0, 2),
+                        (1, -2),
+                        (1, -1),
+                        (1, 0),
+                        (1, 1),
+                        (1, 2),
+                    ],
+                    Grid::Triangular => &[
+                        (-1, -2),
+                        (-1, -1),
+                        (-1, 0),
+                        (-1, 1),
+                        (-1, 2),
+                        (0, -2),
+                        (0, -1),
+                        (0, 1),
+                        (0, 2),
+                        (1, -1),
+                        (1, 0),
+                        (1, 1),
+                    ],
+                }
+            }
+
+            // whether joining the edges of a board as `topology` does keeps the
+            // neighbors of the grid. Mirrored joins lose the skew of hexagonal boards,
+            // triangles have to point the same way on both sides of a join and turn
+            // over where a join mirrors the rows
+            pub fn fits(&self, topology: Topology, height: usize, width: usize) -> bool {
+                let even = |length: usize| length.is_multiple_of(2);
+                match (self, topology) {
+                    (Grid::Square, _) | (_, Topology::Plane) => true,
+                    (_, Topology::CrossSurface) => false,
+                    (Grid::Hexagonal, Topology::KleinBottle) => false,
+                    (Grid::Hexagonal, _) => true,
+                    (Grid::Triangular, Topology::Torus) => even(height) && even(width),
+                    (Grid::Triangular, Topology::HorizontalCylinder) => even(width),
+                    (Grid::Triangular, Topology::VerticalCylinder) => even(height),
+                    (Grid::Triangular, Topology::KleinBottle) => even(width) && !even(height),
+                }
+            }
+
+            // the rule suffix, as in Golly's "B2/S34H"
+            fn suffix(&self) -> &'static str {
+                match self {
+                    Grid::Square => "",
+                    Grid::Hexagonal => "H",
+                    Grid::Triangular => "L",
+                }
+            }
+        }
+
+        impl std::fmt::Display for Grid {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Grid::Square => write!(f, "square"),
+                    Grid::Hexagonal => write!(f, "hexagonal"),
+                    Grid::Triangular => write!(f, "triangular"),
+                }
+            }
+        }
+
+        // the 8 neighbors as bits of a neighbor configuration
+        const N: u8 = 1 << 0;
+        const NE: u8 = 1 << 1;
+        const E: u8 = 1 << 2;
+        const SE: u8 = 1 << 3;
+        const S: u8 = 1 << 4;
+        const SW: u8 = 1 << 5;
+        const W: u8 = 1 << 6;
+        const NW: u8 = 1 << 7;
+
+        // the Hensel letters of up to 4 live neighbors, each with one of the
+        // configurations it names, the others being its rotations and reflections
+        const HENSEL_LETTERS: [&[(char, u8)]; 5] = [
+            &[],
+            &[('c', NE), ('e', N)],
+            &[
+                ('c', NE | SE),
+                ('e', N | E),
+                ('k', N | SE),
+                ('a', N | NE),
+                ('i', N | S),
+                ('n', NE | SW),
+            ],
+            &[
+                ('c', NE | SE | SW),
+                ('e', N | E | W),
+                ('k', N | NE | SE),
+                ('a', N | W | NW),
+                ('i', NW | N | NE),
+                ('n', N | E | SE),
+                ('y', NE | SE | W),
+                ('q', N | SE | NW),
+                ('j', N | NE | S),
+                ('r', N | E | SW),
+            ],
+            &[
+                ('c', NE | SE | SW | NW),
+                ('e', N | E | S | W),
+                ('k', NW | NE | SE | W),
+                ('a', NW | N | NE | E),
+                ('i', NW | NE | E | W),
+                ('n', NW | N | NE | SE),
+                ('y', NW | NE | E | S),
+                ('q', NW | N | W | SE),
+                ('j', NW | N | E | W),
+                ('r', N | E | W | SE),
+                ('t', NW | N | NE | S),
+                ('w', NE | E | S | SW),
+                ('z', N | NE | S | SW),
+            ],
+        ];
+
+        // the letters of counts above 4 name the complements of the letters of 8 - count
+        fn hensel_letters(count: usize) -> Vec<(char, u8)> {
+            if count <= 4 {
+                HENSEL_LETTERS[count].to_vec()
+            } else {
+                HENSEL_LETTERS[MAX_NEIGHBORS - count]
+                    .iter()
+                    .map(|(letter, configuration)| (*letter, !configuration))
+                    .collect()
+            }
+        }
+
+        // the configurations equal to `configuration` under rotation and reflection
+        fn symmetries(configuration: u8) -> impl Iterator<Item = u8> {
+            // a quarter turn moves each neighbor two places around the ring, a
+            // reflection reverses the ring about the N and S neighbors
+            let reflection = configuration.reverse_bits().rotate_left(1);
+            (0..4).flat_map(move |turns| {
+                [
+                    configuration.rotate_left(2 * turns),
+                    reflection.rotate_left(2 * turns),
+                ]
+            })
+        }
+
+        // the live neighbor counts at which a cell is born or survives
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum Counts {
+            // any subset of the counts of the neighborhood of the grid
+            Set([bool; MAX_GRID_NEIGHBORS + 1]),
+            // an inclusive range of counts of a Larger than Life neighborhood
+            Range(u32, u32),
+            // any subset of the 256 configurations of the 8 cell neighborhood, for
+            // isotropic non-totalistic rules
+            Configurations([u64; 4]),
+        }
+
+        impl Counts {
+            fn contains(&self, count: usize) -> bool {
+                match self {
+                    Counts::Set(counts) => counts.get(count).copied().unwrap_or(false),
+                    Counts::Range(min, max) => (*min as usize..=*max as usize).contains(&count),
+                    Counts::Configurations(_) => (0..=u8::MAX).any(|configuration| {
+                        configuration.count_ones() as usize == count
+                            && self.contains_configuration(configuration)
+                    }),
+                }
+            }
+
+            fn contains_configuration(&self, configuration: u8) -> bool {
+                match self {
+                    Counts::Configurations(configurations) => {
+                        configurations[configuration as usize / 64] >> (configuration % 64) & 1 == 1
+                    }
+                    _ => self.contains(configuration.count_ones() as usize),
+                }
+            }
+
+            // Set counts if every count has all or none of its configurations
+            fn from_configurations(configurations: [u64; 4]) -> Self {
+                let counts = Counts::Configurations(configurations);
+                let mut totalistic = [false; MAX_GRID_NEIGHBORS + 1];
+                for (count, included) in totalistic.iter_mut().enumerate().take(MAX_NEIGHBORS + 1) {
+                    let mut configurations = (0..=u8::MAX)
+                        .filter(|configuration| configuration.count_ones() as usize == count)
+                        .map(|configuration| counts.contains_configuration(configuration));
+                    let first = configurations
+                        .next()
+                        .expect("every count has a configuration");
+                    if configurations.any(|contained| contained != first) {
+                        return counts;
+                    }
+                    *included = first;
+                }
+                Counts::Set(totalistic)
+            }
+        }
+
+        // Life-like rule: a dead cell is born if its live neighbor count is in `birth`,
+        // a live cell survives if its count is in `survival`. Generations rules have
+        // more than two states, a live cell that does not survive then passes through
+        // `states - 2` dying states before it is dead. Larger than Life rules count
+        // the live cells of a larger neighborhood, optionally including the cell itself.
+        // Isotropic non-totalistic rules tell apart the shapes of the live neighbors.
+        // Multi-color rules have more than one species of live cells
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct Rule {
+            birth: Counts,
+            survival: Counts,
+            states: u8,
+            range: u16,
+            neighborhood: Neighborhood,
+            middle: bool,
+            grid: Grid,
+            species: u8,
+            // the rule as it was given if it is a range 1 Larger than Life rule,
+            // which is stepped as its Life-like counterpart
+            range_one: Option<RangeOne>,
+        }
+
+        // the Larger than Life counts of a range 1 rule, inclusive (min, max)
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct RangeOne {
+            neighborhood: Neighborhood,
+            middle: bool,
+            survival: (u32, u32),
+            birth: (u32, u32),
+        }
+
+        // the multi-color variants, by number of species
+        const SPECIES_NAMES: [(u8, &str); 2] = [(2, "Immigration"), (4, "QuadLife")];
+
+        impl Rule {
+            pub fn new(birth: &[usize], survival: &[usize]) -> Result<Self, RuleParseError> {
+                Self::generations(birth, survival, 2)
+            }
+
+            pub fn generations(
+                birth: &[usize],
+                survival: &[usize],
+                states: u8,
+            ) -> Result<Self, RuleParseError> {
+                Self::on_grid(Grid::Square, birth, survival, states)
+            }
+
+            // a rule counting the neighbors of `grid`
+            pub fn on_grid(
+                grid: Grid,
+                birth: &[usize],
+                survival: &[usize],
+                states: u8,
+            ) -> Result<Self, RuleParseError> {
+                if states < 2 {
+                    return Err(RuleParseError::StateCountOutOfRange(states as usize));
+                }
+                let counts = |neighbor_counts: &[usize]| {
+                    let mut counts = [false; MAX_GRID_NEIGHBORS + 1];
+                    for &count in neighbor_counts {
+                        if count > grid.neighbors() {
+                            return Err(RuleParseError::NeighborCountOutOfRange(count));
+                        }
+                        counts[count] = true;
+                    }
+                    Ok(Counts::Set(counts))
+                };
+                Ok(Self {
+                    birth: counts(birth)?,
+                    survival: counts(survival)?,
+                    states,
+                    range: 1,
+                    neighborhood: Neighborhood::Moore,
+                    middle: false,
+                    grid,
+                    species: 1,
+                    range_one: None,
+                })
+            }
+
+            pub fn larger_than_life(
+                range: usize,
+                neighborhood: Neighborhood,
+                middle: bool,
+                survival: RangeInclusive<u32>,
+                birth: RangeInclusive<u32>,
+                states: u8,
+            ) -> Result<Self, RuleParseError> {
+                if !(1..=MAX_RANGE).contains(&range) {
+                    return Err(RuleParseError::RangeOutOfRange(range));
+                }
+                // the middle cell counts too if it is included
+                let size = neighborhood.size(range) + middle as usize;
+                for counts in [&survival, &birth] {
+                    let (min, max) = (*counts.start(), *counts.end());
+                    if min > max {
+                        return Err(RuleParseError::EmptyCountRange(min, max));
+                    }
+                    if max as usize > size {
+                        return Err(RuleParseError::NeighborhoodCountOutOfRange(max, size));
+                    }
+                }
+                // range 1 Moore and circular neighborhoods are the 8 cell neighborhood,
+                // stored as Life-like counts so that they can be stepped just as fast
+                if range == 1 && neighborhood != Neighborhood::VonNeumann {
+                    let survival_counts: Vec<usize> = survival
+                        .clone()
+                        .filter_map(|count| {
+                            if middle {
+                                count.checked_sub(1)
+                            } else {
+                                Some(count)
+                            }
+                        })
+                        .map(|count| count as usize)
+                        .collect();
+                    let birth_counts: Vec<usize> =
+                        birth.clone().map(|count| count as usize).collect();
+                    let rule = Self::generations(&birth_counts, &survival_counts, states)?;
+                    return Ok(Self {
+                        range_one: Some(RangeOne {
+                            neighborhood,
+                            middle,
+                            survival: (*survival.start(), *survival.end()),
+                            birth: (*birth.start(), *birth.end()),
+                        }),
+                        ..rule
+                    });
+                }
+                if states < 2 {
+                    return Err(RuleParseError::StateCountOutOfRange(states as usize));
+                }
+                Ok(Self {
+                    birth: Counts::Range(*birth.start(), *birth.end()),
+                    survival: Counts::Range(*survival.start(), *survival.end()),
+                    states,
+                    range: range as u16,
+                    neighborhood,
+                    middle,
+                    grid: Grid::Square,
+                    species: 1,
+                    range_one: None,
+                })
+            }
+
+            // a rule of the 8 cell neighborhood that is isotropic non-totalistic
+            // unless both `birth` and `survival` are Set counts
+            fn from_counts(birth: Counts, survival: Counts) -> Self {
+                let (birth, survival) = match (birth, survival) {
+                    (Counts::Set(_), Counts::Set(_)) => (birth, survival),
+                    _ => (
+                        Counts::Configurations(configurations(birth)),
+                        Counts::Configurations(configurations(survival)),
+                    ),
+                };
+                Self {
+                    birth,
+                    survival,
+                    states: 2,
+                    range: 1,
+                    neighborhood: Neighborhood::Moore,
+                    middle: false,
+                    grid: Grid::Square,
+                    species: 1,
+                    range_one: None,
+                }
+            }
+
+            // the multi-color variant of a two state rule, with 2 species as in
+            // Immigration or 4 as in QuadLife; newborn cells take the species of
+            // most of their live neighbors
+            pub fn with_species(self, species: u8) -> Result<Self, RuleParseError> {
+                let named = SPECIES_NAMES.iter().any(|(count, _)| *count == species);
+                if (species != 1 && !named) || (species > 1 && self.states > 2) {
+                    return Err(RuleParseError::UnsupportedSpecies(species as usize));
+                }
+                if species > 1 && self.is_larger_than_life() {
+                    return Err(RuleParseError::UnsupportedSpecies(species as usize));
+                }
+                Ok(Self { species, ..self })
+            }
+
+            pub fn conway() -> Self {
+                Self::new(&[3], &[2, 3]).expect("B3/S23 is a valid rule")
+            }
+
+            pub fn born(&self, live_neighbors: usize) -> bool {
+                self.birth.contains(live_neighbors)
+            }
+
+            // for rules that include the middle, `live_neighbors` counts the cell itself
+            pub fn survives(&self, live_neighbors: usize) -> bool {
+                self.survival.contains(live_neighbors)
+            }
+
+            // 2 for Life-like rules
+            pub fn states(&self) -> u8 {
+                self.states
+            }
+
+            // two states and the 8 cell neighborhood, counted totalistically
+            // S/B notation ("23/3", "345/2/4") as used by Life 1.05 files, multi-color
+            // and Larger than Life rules have no S/B form and keep their notation
+            pub fn survival_birth(&self) -> String {
+                struct SurvivalBirth(Rule);
+
+                impl std::fmt::Display for SurvivalBirth {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        let SurvivalBirth(rule) = self;
+                        write_counts(f, &rule.survival, rule.grid)?;
+                        write!(f, "/")?;
+                        write_counts(f, &rule.birth, rule.grid)?;
+                        if rule.states > 2 {
+                            write!(f, "/{}", rule.states)?;
+                        }
+                        write!(f, "{}", rule.grid.suffix())
+                    }
+                }
+
+                if self.species > 1 || self.is_larger_than_life() || self.range_one.is_some() {
+                    return self.to_string();
+                }
+                SurvivalBirth(*self).to_string()
+            }
+
+            pub fn is_life_like(&self) -> bool {
+                self.states == 2
+                    && matches!(self.birth, Counts::Set(_))
+                    && self.grid == Grid::Square
+                    && self.species == 1
+            }
+
+            // 1 unless the rule is a multi-color variant
+            pub fn species(&self) -> u8 {
+                self.species
+            }
+
+            pub fn grid(&self) -> Grid {
+                self.grid
+            }
+
+            pub fn is_isotropic(&self) -> bool {
+                matches!(self.birth, Counts::Configurations(_))
+            }
+
+            pub fn is_larger_than_life(&self) -> bool {
+                matches!(self.birth, Counts::Range(..))
+            }
+
+            pub fn range(&self) -> usize {
+                self.range as usize
+            }
+
+            pub fn neighborhood(&self) -> Neighborhood {
+                self.neighborhood
+            }
+
+            pub fn includes_middle(&self) -> bool {
+                self.middle
+            }
+
+            pub fn action(&self, cell: Cell, live_neighbors: usize) -> Action {
+                self.transition(
+                    cell,
+                    self.born(live_neighbors),
+                    self.survives(live_neighbors),
+                )
+            }
+
+            // bit i of `configuration` is set if the i-th of the N, NE, E, SE, S, SW,
+            // W and NW neighbors is alive
+            pub fn configuration_action(&self, cell: Cell, configuration: u8) -> Action {
+                self.transition(
+                    cell,
+                    self.birth.contains_configuration(configuration),
+                    self.survival.contains_configuration(configuration),
+                )
+            }
+
+            fn transition(&self, cell: Cell, born: bool, survives: bool) -> Action {
+                match cell {
+                    Cell::Dead if born => Action::Live,
+                    Cell::Alive | Cell::Species(_) if survives => Action::Live,
+                    Cell::Alive | Cell::Species(_) => self.decay(0),
+                    Cell::Dead => Action::Die,
+                    Cell::Dying(age) => self.decay(age),
+                }
+            }
+
+            // the state after `age` generations of dying, counting the live state as 0
+            fn decay(&self, age: u8) -> Action {
+                if (age as usize) + 2 < self.states as usize {
+                    Action::Decay(age + 1)
+                } else {
+                    Action::Die
+                }
+            }
+        }
+
+        impl Default for Rule {
+            fn default() -> Self {
+                Self::conway()
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum RuleParseError {
+            MissingSeparator,
+            UnexpectedCharacter(char),
+            NeighborCountOutOfRange(usize),
+            StateCountOutOfRange(usize),
+            RangeOutOfRange(usize),
+            // a Larger than Life count past the cells of the neighborhood, and
+            // how many there are
+            NeighborhoodCountOutOfRange(u32, usize),
+            EmptyCountRange(u32, u32),
+            InvalidField(String),
+            MissingField(char),
+            UnsupportedSpecies(usize),
+        }
+
+        impl std::fmt::Display for RuleParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::MissingSeparator => {
+                        write!(
+                            f,
+                            "rule must have the form B<digits>/S<digits> or <S digits>/<B digits>"
+                        )
+                    }
+                    Self::UnexpectedCharacter(c) => {
+                        write!(f, "unexpected character in rule: '{}'", c)
+                    }
+                    Self::NeighborCountOutOfRange(count) => write!(
+                        f,
+                        "neighbor count {} is out of range, must be at most {} ({} on hexagonal and {} on triangular grids)",
+                        count,
+                        MAX_NEIGHBORS,
+                        Grid::Hexagonal.neighbors(),
+                        Grid::Triangular.neighbors()
+                    ),
+                    Self::StateCountOutOfRange(states) => write!(
+                        f,
+                        "number of states {} is out of range, must be between 2 and {}",
+                        states,
+                        u8::MAX
+                    ),
+                    Self::RangeOutOfRange(range) => write!(
+                        f,
+                        "range {} is out of range, must be between 1 and {}",
+                        range, MAX_RANGE
+                    ),
+                    Self::NeighborhoodCountOutOfRange(count, size) => write!(
+                        f,
+                        "count {} is out of range, the neighborhood has {} cells",
+                        count, size
+                    ),
+                    Self::EmptyCountRange(min, max) => write!(
+                        f,
+                        "count range {}..{} is empty, its minimum is larger than its maximum",
+                        min, max
+                    ),
+                    Self::InvalidField(field) => {
+                        write!(f, "invalid Larger than Life field: '{}'", field)
+                    }
+                    Self::MissingField(key) => {
+                        write!(f, "Larger than Life rule is missing its {} field", key)
+                    }
+                    Self::UnsupportedSpecies(species) => write!(
+                        f,
+                        "{} species are not supported, multi-color rules have 2 (Immigration) or 4 (QuadLife) species of a two state rule",
+                        species
+                    ),
+                }
+            }
+        }
+
+        impl std::error::Error for RuleParseError {}
+
+        fn configurations(counts: Counts) -> [u64; 4] {
+            let mut configurations = [0; 4];
+            for configuration in (0..=u8::MAX).filter(|c| counts.contains_configuration(*c)) {
+                configurations[configuration as usize / 64] |= 1 << (configuration % 64);
+            }
+            configurations
+        }
+
+        // neighbor counts, each optionally followed by the Hensel letters of the
+        // configurations it is limited to or, after a '-', excludes ("2-a", "34q")
+        fn parse_counts(digits: &str) -> Result<Counts, RuleParseError> {
+            let mut configurations = [0; 4];
+            let mut chars = digits.chars().peekable();
+            while let Some(c) = chars.next() {
+                let count = c
+                    .to_digit(10)
+                    .ok_or(RuleParseError::UnexpectedCharacter(c))?
+                    as usize;
+                if count > MAX_NEIGHBORS {
+                    return Err(RuleParseError::NeighborCountOutOfRange(count));
+                }
+                let excluded = chars.next_if_eq(&'-').is_some();
+                let named = hensel_letters(count);
+                let mut letters = Vec::new();
+                while let Some(letter) = chars.next_if(char::is_ascii_alphabetic) {
+                    let letter = letter.to_ascii_lowercase();
+                    if !named.iter().any(|(named, _)| *named == letter) {
+                        return Err(RuleParseError::UnexpectedCharacter(letter));
+                    }
+                    letters.push(letter);
+                }
+                if excluded && letters.is_empty() {
+                    return Err(RuleParseError::UnexpectedCharacter('-'));
+                }
+                let included: Vec<u8> = if letters.is_empty() {
+                    (0..=u8::MAX)
+                        .filter(|configuration| configuration.count_ones() as usize == count)
+                        .collect()
+                } else {
+                    named
+                        .iter()
+                        .filter(|(letter, _)| letters.contains(letter) != excluded)
+                        .flat_map(|(_, configuration)| symmetries(*configuration))
+                        .collect()
+                };
+                for configuration in included {
+                    configurations[configuration as usize / 64] |= 1 << (configuration % 64);
+                }
+            }
+            Ok(Counts::from_configurations(configurations))
+        }
+
+        // counts of the hexagonal and triangular grids, with A, B and C for the
+        // counts from 10 to 12 of triangles
+        fn parse_grid_counts(digits: &str) -> Result<Vec<usize>, RuleParseError> {
+            digits
+                .chars()
+                .map(|c| match c.to_digit(MAX_GRID_NEIGHBORS as u32 + 1) {
+                    Some(digit) => Ok(digit as usize),
+                    None => Err(RuleParseError::UnexpectedCharacter(c)),
+                })
+                .collect()
+        }
+
+        // the counts of `counts` in Hensel notation, with the letters of a partly
+        // included count or, if shorter, the letters it excludes after a '-', in
+        // alphabetical order as Golly writes them
+        fn write_counts(
+            f: &mut std::fmt::Formatter<'_>,
+            counts: &Counts,
+            grid: Grid,
+        ) -> std::fmt::Result {
+            if grid != Grid::Square {
+                for count in (0..=grid.neighbors()).filter(|count| counts.contains(*count)) {
+                    let digit = char::from_digit(count as u32, MAX_GRID_NEIGHBORS as u32 + 1)
+                        .expect("count is at most 12");
+                    write!(f, "{}", digit.to_ascii_uppercase())?;
+                }
+                return Ok(());
+            }
+            for count in 0..=MAX_NEIGHBORS {
+                let (included, excluded): (Vec<_>, Vec<_>) = hensel_letters(count)
+                    .into_iter()
+                    .partition(|(_, configuration)| counts.contains_configuration(*configuration));
+                let letters = |letters: Vec<(char, u8)>| -> String {
+                    let mut letters: Vec<char> =
+                        letters.iter().map(|(letter, _)| *letter).collect();
+                    letters.sort_unstable();
+                    letters.into_iter().collect()
+                };
+                if !counts.contains(count) {
+                    continue;
+                } else if excluded.is_empty() {
+                    write!(f, "{}", count)?;
+                } else if included.len() <= excluded.len() {
+                    write!(f, "{}{}", count, letters(included))?;
+                } else {
+                    write!(f, "{}-{}", count, letters(excluded))?;
+                }
+            }
+            Ok(())
+        }
+
+        // Golly's Larger than Life notation, e.g. "R5,C0,M1,S34..58,B34..45,NM"
+        fn parse_larger_than_life(s: &str) -> Result<Rule, RuleParseError> {
+            let mut range = None;
+            let mut states = 2;
+            let mut middle = false;
+            let mut survival = None;
+            let mut birth = None;
+            let mut neighborhood = Neighborhood::Moore;
+            for field in s.split(',').map(str::trim) {
+                let invalid = || RuleParseError::InvalidField(field.to_owned());
+                let count_range = |value: &str| -> Result<RangeInclusive<u32>, RuleParseError> {
+                    let (min, max) = value.split_once("..").unwrap_or((value, value));
+                    Ok(min.parse().map_err(|_| invalid())?..=max.parse().map_err(|_| invalid())?)
+                };
+                let Some(key) = field.chars().next() else {
+                    return Err(invalid());
+                };
+                let value = &field[1..];
+                match key {
+                    'R' => range = Some(value.parse::<usize>().map_err(|_| invalid())?),
+                    'C' => {
+                        let count = value.parse::<usize>().map_err(|_| invalid())?;
+                        states = u8::try_from(count.max(2))
+                            .map_err(|_| RuleParseError::StateCountOutOfRange(count))?;
+                    }
+                    'M' => {
+                        middle = match value {
+                            "0" => false,
+                            "1" => true,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    'S' => survival = Some(count_range(value)?),
+                    'B' => birth = Some(count_range(value)?),
+                    'N' => {
+                        neighborhood = match value {
+                            "M" => Neighborhood::Moore,
+                            "N" => Neighborhood::VonNeumann,
+                            "C" => Neighborhood::Circular,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            Rule::larger_than_life(
+                range.ok_or(RuleParseError::MissingField('R'))?,
+                neighborhood,
+                middle,
+                survival.ok_or(RuleParseError::MissingField('S'))?,
+                birth.ok_or(RuleParseError::MissingField('B'))?,
+                states,
+            )
+        }
+
+        impl FromStr for Rule {
+            type Err = RuleParseError;
+
+            // accepts B/S notation ("B36/S23", "b2/s", "B3S23") as well as the
+            // older S/B notation ("23/3"), Generations rules add the number of
+            // states as a third part ("B2/S/C3", "345/2/4"), Hensel letters
+            // after the counts give isotropic non-totalistic rules ("B2-a/S12"),
+            // and an H or L suffix selects the hexagonal or triangular grid
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                let upper = s.to_ascii_uppercase();
+                // "Immigration" and "QuadLife", optionally with a rule other than
+                // Conway's after a colon ("Immigration:B36/S23")
+                for (species, name) in SPECIES_NAMES {
+                    if let Some(rest) = upper.strip_prefix(&name.to_ascii_uppercase()) {
+                        let rule = match rest.trim().strip_prefix(':') {
+                            Some(rule) => Self::from_str(rule)?,
+                            None if rest.trim().is_empty() => Self::conway(),
+                            None => return Err(RuleParseError::MissingSeparator),
+                        };
+                        return rule.with_species(species);
+                    }
+                }
+                if upper.starts_with('R') && upper.contains(',') {
+                    return parse_larger_than_life(&upper);
+                }
+                let (mut upper, grid) = if let Some(rest) = upper.strip_suffix('H') {
+                    (rest, Grid::Hexagonal)
+                } else if let Some(rest) = upper.strip_suffix('L') {
+                    (rest, Grid::Triangular)
+                } else {
+                    (upper.as_str(), Grid::Square)
+                };
+                let mut states = 2;
+                if upper.matches('/').count() == 2 {
+                    let (rest, state_count) = upper.rsplit_once('/').expect("rule has two slashes");
+                    let state_count = state_count
+                        .strip_prefix(['C', 'G'])
+                        .unwrap_or(state_count)
+                        .parse::<usize>()
+                        .map_err(|_| RuleParseError::MissingSeparator)?;
+                    states = u8::try_from(state_count)
+                        .ok()
+                        .filter(|states| *states >= 2)
+                        .ok_or(RuleParseError::StateCountOutOfRange(state_count))?;
+                    upper = rest;
+                }
+                let (birth, survival) = if let Some(rest) = upper.strip_prefix('B') {
+                    let (birth, survival) = match rest.split_once('/') {
+                        Some((birth, survival)) => (birth, survival),
+                        None => rest
+                            .split_once('S')
+                            .ok_or(RuleParseError::MissingSeparator)?,
+                    };
+                    (birth, survival.strip_prefix('S').unwrap_or(survival))
+                } else if let Some(rest) = upper.strip_prefix('S') {
+                    let (survival, birth) = rest
+                        .split_once('/')
+                        .ok_or(RuleParseError::MissingSeparator)?;
+                    let birth = birth
+                        .strip_prefix('B')
+                        .ok_or(RuleParseError::MissingSeparator)?;
+                    (birth, survival)
+                } else {
+                    let (survival, birth) = upper
+                        .split_once('/')
+                        .ok_or(RuleParseError::MissingSeparator)?;
+                    (birth, survival)
+                };
+                match grid {
+                    Grid::Square => Ok(Self {
+                        states,
+                        ..Self::from_counts(parse_counts(birth)?, parse_counts(survival)?)
+                    }),
+                    _ => Self::on_grid(
+                        grid,
+                        &parse_grid_counts(birth)?,
+                        &parse_grid_counts(survival)?,
+                        states,
+                    ),
+                }
+            }
+        }
+
+        impl std::fmt::Display for Rule {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                if let Some((_, name)) = SPECIES_NAMES
+                    .iter()
+                    .find(|(species, _)| *species == self.species)
+                {
+                    let rule = Self {
+                        species: 1,
+                        ..*self
+                    };
+                    return if rule == Self::conway() {
+                        write!(f, "{}", name)
+                    } else {
+                        write!(f, "{}:{}", name, rule)
+                    };
+                }
+                let larger_than_life = match (self.birth, self.survival, self.range_one) {
+                    (Counts::Range(birth_min, birth_max), Counts::Range(min, max), _) => Some((
+                        self.range,
+                        self.neighborhood,
+                        self.middle,
+                        (min, max),
+                        (birth_min, birth_max),
+                    )),
+                    (_, _, Some(range_one)) => Some((
+                        1,
+                        range_one.neighborhood,
+                        range_one.middle,
+                        range_one.survival,
+                        range_one.birth,
+                    )),
+                    _ => None,
+                };
+                if let Some((range, neighborhood, middle, survival, birth)) = larger_than_life {
+                    return write!(
+                        f,
+                        "R{},C{},M{},S{}..{},B{}..{},N{}",
+                        range,
+                        if self.states == 2 { 0 } else { self.states },
+                        middle as u8,
+                        survival.0,
+                        survival.1,
+                        birth.0,
+                        birth.1,
+                        neighborhood.letter()
+                    );
+                }
+                write!(f, "B")?;
+                write_counts(f, &self.birth, self.grid)?;
+                write!(f, "/S")?;
+                write_counts(f, &self.survival, self.grid)?;
+                if self.states > 2 {
+                    write!(f, "/C{}", self.states)?;
+                }
+                write!(f, "{}", self.grid.suffix())
+            }
+        }
+    }
+    pub mod rle {
+        use super::cell::{Cell, Slot};
+        use super::rule::Rule;
+        use super::{
+            CellBoard, CellBoardCreationError, FormatErrorVariant, Pattern, check_board_size,
+        };
+
+        const MAX_LINE_LENGTH: usize = 70;
+
+        struct Header {
+            width: usize,
+            height: usize,
+            rule: Option<Rule>,
+        }
+
+        fn format_error(variant: FormatErrorVariant) -> CellBoardCreationError {
+            CellBoardCreationError::from(variant)
+        }
+
+        fn parse_header(line: &str) -> Result<Header, CellBoardCreationError> {
+            let mut width = None;
+            let mut height = None;
+            let mut rule = None;
+            // Larger than Life rules contain commas themselves, so the rule field
+            // extends to the end of the line
+            let mut fields: Vec<&str> = Vec::new();
+            let mut rest = line;
+            while let Some((field, remainder)) = rest.split_once(',') {
+                if field.trim_start().starts_with("rule") {
+                    break;
+                }
+                fields.push(field);
+                rest = remainder;
+            }
+            fields.push(rest);
+            for field in fields {
+                let (key, value) = field.split_once('=').ok_or_else(|| {
+                    format_error(FormatErrorVariant::InvalidHeader(line.to_owned()))
+                })?;
+                let value = value.trim();
+                let parse_size = |value: &str| {
+                    value.parse::<usize>().map_err(|_| {
+                        format_error(FormatErrorVariant::InvalidHeader(line.to_owned()))
+                    })
+                };
+                match key.trim() {
+                    "x" => width = Some(parse_size(value)?),
+                    "y" => height = Some(parse_size(value)?),
+                    "rule" => {
+                        rule = Some(value.parse::<Rule>().map_err(|error| {
+                            format_error(FormatErrorVariant::InvalidRule(error))
+                        })?)
+                    }
+                    _ => {}
+                }
+            }
+            match (width, height) {
+                (Some(width), Some(height)) => {
+                    check_board_size(height as u64, width as u64)?;
+                    Ok(Header {
+                        width,
+                        height,
+                        rule,
+                    })
+                }
+                _ => Err(format_error(FormatErrorVariant::InvalidHeader(
+                    line.to_owned(),
+                ))),
+            }
+        }
+
+        pub fn parse(input: &str) -> Result<Pattern, CellBoardCreationError> {
+            let mut header = None;
+            let mut name = None;
+            let mut comments = Vec::new();
+            let mut rule = None;
+            let mut occupied_slots = Vec::new();
+            let (mut row, mut col): (usize, usize) = (0, 0);
+            let mut count: Option<usize> = None;
+            // `p`..`y` prefix of a multi-state tag past state 24 and its column
+            let mut prefix: Option<(char, usize)> = None;
+            let (mut pattern_height, mut pattern_width) = (0, 0);
+            'lines: for (line_index, line) in input.lines().enumerate() {
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                let line = line.trim();
+                let at_line = |error: CellBoardCreationError| error.at(line_index + 1, indent + 1);
+                if let Some(comment) = line.strip_prefix('#') {
+                    let (kind, text) = comment.split_at(comment.len().min(1));
+                    let text = text.trim().to_owned();
+                    match kind {
+                        "N" => name = Some(text),
+                        "C" | "c" => comments.push(text),
+                        "r" => {
+                            rule = Some(text.parse::<Rule>().map_err(|error| {
+                                at_line(format_error(FormatErrorVariant::InvalidRule(error)))
+                            })?)
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                if header.is_none()
+                    && occupied_slots.is_empty()
+                    && row == 0
+                    && line.starts_with('x')
+                {
+                    header = Some(parse_header(line).map_err(at_line)?);
+                    continue;
+                }
+                let pattern_rule = header
+                    .as_ref()
+                    .and_then(|header: &Header| header.rule)
+                    .or(rule);
+                let multi_state = pattern_rule.is_some_and(is_multi_state);
+                // a prefix has to be followed by the letter of its tag
+                let dangling = |(prefix, column): (char, usize)| {
+                    format_error(FormatErrorVariant::UnrecognizedCharacter(prefix))
+                        .at(line_index + 1, indent + column + 1)
+                };
+                for (column, c) in line.chars().enumerate() {
+                    let out_of_range = || {
+                        format_error(FormatErrorVariant::RunCountOutOfRange)
+                            .at(line_index + 1, indent + column + 1)
+                    };
+                    if let Some(prefix) = prefix
+                        && !c.is_ascii_uppercase()
+                    {
+                        return Err(dangling(prefix));
+                    }
+                    match c {
+                        '0'..='9' => {
+                            let digit = c.to_digit(10).expect("matched an ascii digit") as usize;
+                            count = Some(
+                                count
+                                    .unwrap_or(0)
+                                    .checked_mul(10)
+                                    .and_then(|count| count.checked_add(digit))
+                                    .ok_or_else(out_of_range)?,
+                            );
+                        }
+                        'b' | '.' => {
+                            col = col
+                                .checked_add(count.take().unwrap_or(1))
+                                .ok_or_else(out_of_range)?;
+                        }
+                        '$' => {
+                            row = row
+                                .checked_add(count.take().unwrap_or(1))
+                                .ok_or_else(out_of_range)?;
+                            col = 0;
+                        }
+                        '!' => break 'lines,
+                        'p'..='y' if multi_state => prefix = Some((c, column)),
+                        c if c.is_ascii_alphabetic() => {
+                            let cell = match pattern_rule {
+                                Some(rule) if multi_state && c.is_ascii_uppercase() => {
+                                    let plane = prefix
+                                        .take()
+                                        .map_or(0, |(prefix, _)| (prefix as u8 - b'o') as usize);
+                                    let state = plane * 24 + (c as u8 - b'A') as usize + 1;
+                                    // only `A`..`X` are tags, `Y` and `Z` would be the
+                                    // next plane's first states
+                                    let tagged = if c <= 'X' {
+                                        tag_cell(state, rule)
+                                    } else {
+                                        Err(FormatErrorVariant::StateOutOfRange(state))
+                                    };
+                                    tagged.map_err(|variant| {
+                                        format_error(variant)
+                                            .at(line_index + 1, indent + column + 1)
+                                    })?
+                                }
+                                _ => Cell::Alive,
+                            };
+                            // the run has to fit into a board before its cells are kept
+                            let end = col
+                                .checked_add(count.take().unwrap_or(1))
+                                .ok_or_else(out_of_range)?;
+                            check_board_size(row as u64 + 1, end as u64).map_err(at_line)?;
+                            for col in col..end {
+                                occupied_slots.push((Slot(row, col), cell));
+                            }
+                            col = end;
+                            pattern_height = pattern_height.max(row + 1);
+                            pattern_width = pattern_width.max(col);
+                        }
+                        c if c.is_whitespace() => {}
+                        c => {
+                            return Err(format_error(FormatErrorVariant::UnrecognizedCharacter(c))
+                                .at(line_index + 1, indent + column + 1));
+                        }
+                    }
+                }
+                if let Some(prefix) = prefix {
+                    return Err(dangling(prefix));
+                }
+            }
+
+            let (height, width) = match &header {
+                Some(header) => (header.height, header.width),
+                None => (pattern_height, pattern_width),
+            };
+            if pattern_height > height || pattern_width > width {
+                return Err(format_error(FormatErrorVariant::PatternExceedsSize {
+                    height,
+                    width,
+                }));
+            }
+            let mut cell_board = CellBoard::new(height, width);
+            for (slot, cell) in occupied_slots {
+                cell_board.set_slot(slot, cell);
+            }
+            Ok(Pattern {
+                cell_board,
+                rule: header.and_then(|header| header.rule).or(rule),
+                name,
+                comments,
+            })
+        }
+
+        fn push_run(body: &mut String, line_length: &mut usize, count: usize, tag: &str) {
+            if count == 0 {
+                return;
+            }
+            let run = if count == 1 {
+                tag.to_owned()
+            } else {
+                format!("{}{}", count, tag)
+            };
+            if *line_length + run.len() > MAX_LINE_LENGTH {
+                body.push('\n');
+                *line_length = 0;
+            }
+            body.push_str(&run);
+            *line_length += run.len();
+        }
+
+        // Generations and multi-color rules use Golly's multi-state tags: `.` for
+        // dead cells and `A`..`X` for the states from 1 on, prefixed with `p`..`y`
+        // past state 24
+        fn state_tag(state: usize) -> String {
+            let letter = (b'A' + ((state - 1) % 24) as u8) as char;
+            match (state - 1) / 24 {
+                0 => letter.to_string(),
+                prefix => format!("{}{}", (b'o' + prefix as u8) as char, letter),
+            }
+        }
+
+        fn cell_tag(cell: Cell, multi_state: bool) -> String {
+            match (cell, multi_state) {
+                (Cell::Dead, false) | (Cell::Dying(_), false) => "b".to_owned(),
+                (Cell::Alive, false) | (Cell::Species(_), false) => "o".to_owned(),
+                (Cell::Dead, true) => ".".to_owned(),
+                (Cell::Alive, true) => state_tag(1),
+                (Cell::Dying(age), true) => state_tag(age as usize + 1),
+                (Cell::Species(species), true) => state_tag(species as usize),
+            }
+        }
+
+        // the states of multi-color rules are the species, those of Generations
+        // rules the live state and the dying ones
+        fn tag_cell(state: usize, rule: Rule) -> Result<Cell, FormatErrorVariant> {
+            let multi_color = rule.species() > 1;
+            let states = if multi_color {
+                rule.species() as usize + 1
+            } else {
+                rule.states() as usize
+            };
+            match state {
+                0 => Ok(Cell::Dead),
+                state if state >= states => Err(FormatErrorVariant::StateOutOfRange(state)),
+                state if multi_color => Ok(Cell::Species(state as u8)),
+                1 => Ok(Cell::Alive),
+                state => Ok(Cell::Dying(state as u8 - 1)),
+            }
+        }
+
+        fn is_multi_state(rule: Rule) -> bool {
+            rule.states() > 2 || rule.species() > 1
+        }
+
+        pub fn write(cell_board: &CellBoard, rule: Rule) -> String {
+            let multi_state = is_multi_state(rule);
+            let mut body = String::new();
+            let mut line_length = 0;
+            let mut pending_rows = 0;
+            for row in 0..cell_board.height() {
+                let cells: Vec<Cell> = (0..cell_board.width())
+                    .map(|col| match cell_board.get_slot(Slot(row, col)) {
+                        Cell::Dying(_) if !multi_state => Cell::Dead,
+                        cell => cell,
+                    })
+                    .collect();
+                let Some(last_occupied) = cells.iter().rposition(|cell| *cell != Cell::Dead) else {
+                    pending_rows += 1;
+                    continue;
+                };
+                push_run(&mut body, &mut line_length, pending_rows, "$");
+                pending_rows = 1;
+                let mut cells = cells[..=last_occupied].iter().peekable();
+                while let Some(&cell) = cells.next() {
+                    let mut count = 1;
+                    while cells.next_if(|next| **next == cell).is_some() {
+                        count += 1;
+                    }
+                    push_run(
+                        &mut body,
+                        &mut line_length,
+                        count,
+                        &cell_tag(cell, multi_state),
+                    );
+                }
+            }
+            push_run(&mut body, &mut line_length, 1, "!");
+            format!(
+                "x = {}, y = {}, rule = {}\n{}\n",
+                cell_board.width(),
+                cell_board.height(),
+                rule,
+                body
+            )
+        }
+    }
+    pub mod topology {
+        use super::cell::Slot;
+        use crate::utils::add_mod_n;
+        use std::str::FromStr;
+
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum Topology {
+            // both pairs of edges are joined
+            #[default]
+            Torus,
+            // cells beyond the edges are always dead
+            Plane,
+            // the left and right edges are joined, top and bottom are dead borders
+            HorizontalCylinder,
+            // the top and bottom edges are joined, left and right are dead borders
+            VerticalCylinder,
+            // left and right are joined, top and bottom are joined with a twist
+            // so that leaving at the top of a column enters at the bottom of the
+            // mirrored column
+            KleinBottle,
+            // both pairs of edges are joined with a twist (projective plane)
+            CrossSurface,
+        }
+
+        impl Topology {
+            pub const ALL: [Topology; 6] = [
+                Topology::Torus,
+                Topology::Plane,
+                Topology::HorizontalCylinder,
+                Topology::VerticalCylinder,
+                Topology::KleinBottle,
+                Topology::CrossSurface,
+            ];
+
+            // the slot `offset` = (dy, dx) away from `slot`, None if that falls off
+            // a dead border
+            pub fn neighbor(
+                &self,
+                slot: Slot,
+                (dy, dx): (i32, i32),
+                height: usize,
+                width: usize,
+            ) -> Option<Slot> {
+                let Slot(row, col) = slot;
+                let (height_i64, width_i64) = (height as i64, width as i64);
+                let (new_row, new_col) = (row as i64 + dy as i64, col as i64 + dx as i64);
+                let row_in_range = (0..height_i64).contains(&new_row);
+                let col_in_range = (0..width_i64).contains(&new_col);
+                let mirrored = |crossings: i64, position: i64, length: i64| {
+                    if crossings % 2 == 0 {
+                        position
+                    } else {
+                        length - 1 - position
+                    }
+                };
+                match self {
+                    Topology::Torus => {
+                        Some(Slot(add_mod_n(row, dy, height), add_mod_n(col, dx, width)))
+                    }
+                    Topology::Plane => (row_in_range && col_in_range)
+                        .then_some(Slot(new_row as usize, new_col as usize)),
+                    Topology::HorizontalCylinder => {
+                        row_in_range.then(|| Slot(new_row as usize, add_mod_n(col, dx, width)))
+                    }
+                    Topology::VerticalCylinder => {
+                        col_in_range.then(|| Slot(add_mod_n(row, dy, height), new_col as usize))
+                    }
+                    Topology::KleinBottle => {
+                        let new_col = new_col.rem_euclid(width_i64);
+                        let crossings = new_row.div_euclid(height_i64);
+                        Some(Slot(
+                            new_row.rem_euclid(height_i64) as usize,
+                            mirrored(crossings, new_col, width_i64) as usize,
+                        ))
+                    }
+                    Topology::CrossSurface => {
+                        let col_crossings = new_col.div_euclid(width_i64);
+                        let new_col = new_col.rem_euclid(width_i64);
+                        let new_row = mirrored(col_crossings, new_row, height_i64);
+                        let row_crossings = new_row.div_euclid(height_i64);
+                        Some(Slot(
+                            new_row.rem_euclid(height_i64) as usize,
+                            mirrored(row_crossings, new_col, width_i64) as usize,
+                        ))
+                    }
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    Topology::Torus => "torus",
+                    Topology::Plane => "plane",
+                    Topology::HorizontalCylinder => "horizontal-cylinder",
+                    Topology::VerticalCylinder => "vertical-cylinder",
+                    Topology::KleinBottle => "klein-bottle",
+                    Topology::CrossSurface => "cross-surface",
+                }
+            }
+        }
+
+        impl std::fmt::Display for Topology {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct TopologyParseError(pub String);
+
+        impl std::fmt::Display for TopologyParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let names: Vec<&str> = Topology::ALL.iter().map(Topology::name).collect();
+                write!(
+                    f,
+                    "unknown topology '{}', expected one of: {}",
+                    self.0,
+                    names.join(", ")
+                )
+            }
+        }
+
+        impl std::error::Error for TopologyParseError {}
+
+        impl FromStr for Topology {
+            type Err = TopologyParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Topology::ALL
+                    .into_iter()
+                    .find(|topology| topology.name().eq_ignore_ascii_case(s.trim()))
+                    .ok_or_else(|| TopologyParseError(s.to_owned()))
+            }
+        }
+    }
+
+    pub mod soup {
+        use super::CellBoard;
+        use super::cell::{Cell, Slot};
+        use rand::Rng;
+        use std::str::FromStr;
+
+        // the symmetries of apgsearch's soups, about the center of the soup region
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum Symmetry {
+            // no symmetry
+            #[default]
+            C1,
+            // unchanged by a half turn
+            C2,
+            // unchanged by a quarter turn, needs a square region
+            C4,
+            // mirrored left to right
+            D2,
+            // mirrored left to right and top to bottom
+            D4,
+            // unchanged by every rotation and reflection of the square, needs a
+            // square region
+            D8,
+        }
+
+        impl Symmetry {
+            pub const ALL: [Symmetry; 6] = [
+                Symmetry::C1,
+                Symmetry::C2,
+                Symmetry::C4,
+                Symmetry::D2,
+                Symmetry::D4,
+                Symmetry::D8,
+            ];
+
+            pub fn needs_square(&self) -> bool {
+                matches!(self, Symmetry::C4 | Symmetry::D8)
+            }
+
+            // the positions in a `height` by `width` region that must equal the one
+            // at `row` and `col`, including itself
+            pub fn images(
+                &self,
+                row: usize,
+                col: usize,
+                height: usize,
+                width: usize,
+            ) -> Vec<(usize, usize)> {
+                let (mirrored_row, mirrored_col) = (height - 1 - row, width - 1 - col);
+                // for square regions only, where `width - 1 - row` is a column
+                let quarter_turns = || {
+                    [
+                        (row, col),
+                        (col, width - 1 - row),
+                        (mirrored_row, mirrored_col),
+                        (width - 1 - col, row),
+                    ]
+                };
+                let mut images = match self {
+                    Symmetry::C1 => vec![(row, col)],
+                    Symmetry::C2 => vec![(row, col), (mirrored_row, mirrored_col)],
+                    Symmetry::C4 => quarter_turns().to_vec(),
+                    Symmetry::D2 => vec![(row, col), (row, mirrored_col)],
+                    Symmetry::D4 => vec![
+                        (row, col),
+                        (row, mirrored_col),
+                        (mirrored_row, col),
+                        (mirrored_row, mirrored_col),
+                    ],
+                    Symmetry::D8 => quarter_turns()
+                        .into_iter()
+                        .flat_map(|(row, col)| [(row, col), (col, row)])
+                        .collect(),
+                };
+                images.sort_unstable();
+                images.dedup();
+                images
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    Symmetry::C1 => "C1",
+                    Symmetry::C2 => "C2",
+                    Symmetry::C4 => "C4",
+                    Symmetry::D2 => "D2",
+                    Symmetry::D4 => "D4",
+                    Symmetry::D8 => "D8",
+                }
+            }
+        }
+
+        impl std::fmt::Display for Symmetry {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+
+        impl FromStr for Symmetry {
+            type Err = SoupParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Symmetry::ALL
+                    .into_iter()
+                    .find(|symmetry| symmetry.name().eq_ignore_ascii_case(s.trim()))
+                    .ok_or_else(|| SoupParseError::Symmetry(s.to_owned()))
+            }
+        }
+
+        // the direction in which the density changes across the region
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Gradient {
+            // from the left column to the right column
+            Horizontal,
+            // from the top row to the bottom row
+            Vertical,
+            // from the center to the corners
+            Radial,
+        }
+
+        impl Gradient {
+            pub const ALL: [Gradient; 3] =
+                [Gradient::Horizontal, Gradient::Vertical, Gradient::Radial];
+
+            // how far along the gradient the position is, from 0 to 1
+            fn progress(&self, row: usize, col: usize, height: usize, width: usize) -> f64 {
+                let fraction = |position: usize, length: usize| {
+                    if length > 1 {
+                        position as f64 / (length - 1) as f64
+                    } else {
+                        0.0
+                    }
+                };
+                match self {
+                    Gradient::Horizontal => fraction(col, width),
+                    Gradient::Vertical => fraction(row, height),
+                    Gradient::Radial => {
+                        let dy = fraction(row, height) - 0.5;
+                        let dx = fraction(col, width) - 0.5;
+                        (dy * dy + dx * dx).sqrt() / 0.5_f64.sqrt()
+                    }
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    Gradient::Horizontal => "horizontal",
+                    Gradient::Vertical => "vertical",
+                    Gradient::Radial => "radial",
+                }
+            }
+        }
+
+        impl std::fmt::Display for Gradient {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.name())
+            }
+        }
+
+        impl FromStr for Gradient {
+            type Err = SoupParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Gradient::ALL
+                    .into_iter()
+                    .find(|gradient| gradient.name().eq_ignore_ascii_case(s.trim()))
+                    .ok_or_else(|| SoupParseError::Gradient(s.to_owned()))
+            }
+        }
+
+        // the rectangle of the board a soup fills, from its top left slot
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct Region {
+            pub row: usize,
+            pub col: usize,
+            pub height: usize,
+            pub width: usize,
+        }
+
+        impl std::fmt::Display for Region {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(
+                    f,
+                    "{},{},{},{}",
+                    self.row, self.col, self.height, self.width
+                )
+            }
+        }
+
+        // "row,col,height,width"
+        impl FromStr for Region {
+            type Err = SoupParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let invalid = || SoupParseError::Region(s.to_owned());
+                let numbers = s
+                    .split(',')
+                    .map(|number| number.trim().parse::<usize>().map_err(|_| invalid()))
+                    .collect::<Result<Vec<usize>, _>>()?;
+                match numbers[..] {
+                    [row, col, height, width] if height > 0 && width > 0 => Ok(Region {
+                        row,
+                        col,
+                        height,
+                        width,
+                    }),
+                    _ => Err(invalid()),
+                }
+            }
+        }
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum SoupParseError {
+            Symmetry(String),
+            Gradient(String),
+            Region(String),
+        }
+
+        impl std::fmt::Display for SoupParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let names = |names: Vec<&str>| names.join(", ");
+                match self {
+                    Self::Symmetry(symmetry) => write!(
+                        f,
+                        "unknown symmetry '{}', expected one of: {}",
+                        symmetry,
+                        names(Symmetry::ALL.iter().map(Symmetry::name).collect())
+                    ),
+                    Self::Gradient(gradient) => write!(
+                        f,
+                        "unknown gradient '{}', expected one of: {}",
+                        gradient,
+                        names(Gradient::ALL.iter().map(Gradient::name).collect())
+                    ),
+                    Self::Region(region) => write!(
+                        f,
+                        "invalid region '{}', expected row,col,height,width with a nonzero height and width",
+                        region
+                    ),
+                }
+            }
+        }
+
+        impl std::error::Error for SoupParseError {}
+
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum SoupError {
+            RegionOutOfBounds {
+                region: Region,
+                height: usize,
+                width: usize,
+            },
+            NonSquareRegion(Symmetry),
+        }
+
+        impl std::fmt::Display for SoupError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::RegionOutOfBounds {
+                        region,
+                        height,
+                        width,
+                    } => write!(
+                        f,
+                        "soup region {} does not fit on the {}x{} board",
+                        region, height, width
+                    ),
+                    Self::NonSquareRegion(symmetry) => {
+                        write!(f, "{} symmetry needs a square soup region", symmetry)
+                    }
+                }
+            }
+        }
+
+        impl std::error::Error for SoupError {}
+
+        // a random fill of a board: live cells with probability `density`, or a
+        // density changing along a gradient, only inside `region` if there is one
+        // and repeated as the symmetry demands
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct Soup {
+            density: f64,
+            region: Option<Region>,
+            symmetry: Symmetry,
+            gradient: Option<(Gradient, f64)>,
+        }
+
+        impl Soup {
+            pub fn new(density: f64) -> Self {
+                Self {
+                    density,
+                    region: None,
+                    symmetry: Symmetry::C1,
+                    gradient: None,
+                }
+            }
+
+            pub fn with_region(mut self, region: Region) -> Self {
+                self.region = Some(region);
+                self
+            }
+
+            pub fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+                self.symmetry = symmetry;
+                self
+            }
+
+            // the density changes from the soup's density to `end_density` along
+            // the gradient
+            pub fn with_gradient(mut self, gradient: Gradient, end_density: f64) -> Self {
+                self.gradient = Some((gradient, end_density));
+                self
+            }
+
+            pub fn symmetry(&self) -> Symmetry {
+                self.symmetry
+            }
+
+            pub fn region(&self) -> Option<Region> {
+                self.region
+            }
+
+            // the gradient with the density at its far end
+            pub fn gradient(&self) -> Option<(Gradient, f64)> {
+                self.gradient
+            }
+
+            // the probability that the cell at `row` and `col` of a `height` by
+            // `width` region is alive
+            pub fn density_at(&self, row: usize, col: usize, height: usize, width: usize) -> f64 {
+                let density = match self.gradient {
+                    Some((gradient, end_density)) => {
+                        let progress = gradient.progress(row, col, height, width);
+                        self.density + (end_density - self.density) * progress
+                    }
+                    None => self.density,
+                };
+                density.clamp(0.0, 1.0)
+            }
+
+            // the region the soup fills on a `height` by `width` board, or why it
+            // cannot be filled there
+            pub fn region_on(&self, height: usize, width: usize) -> Result<Region, SoupError> {
+                let region = self.region.unwrap_or(Region {
+                    row: 0,
+                    col: 0,
+                    height,
+                    width,
+                });
+                let fits = |start: usize, length: usize, size: usize| {
+                    start.checked_add(length).is_some_and(|end| end <= size)
+                };
+                if !fits(region.row, region.height, height)
+                    || !fits(region.col, region.width, width)
+                {
+                    return Err(SoupError::RegionOutOfBounds {
+                        region,
+                        height,
+                        width,
+                    });
+                }
+                if self.symmetry.needs_square() && region.height != region.width {
+                    return Err(SoupError::NonSquareRegion(self.symmetry));
+                }
+                Ok(region)
+            }
+
+            // clears `cell_board` and fills the soup region, live cells being of a
+            // random one of `species` species if there is more than one
+            pub fn fill(
+                &self,
+                cell_board: &mut CellBoard,
+                species: u8,
+                rng: &mut impl Rng,
+            ) -> Result<(), SoupError> {
+                let region = self.region_on(cell_board.height(), cell_board.width())?;
+                cell_board.clear();
+                let mut filled = vec![false; region.height * region.width];
+                for row in 0..region.height {
+                    for col in 0..region.width {
+                        if filled[row * region.width + col] {
+                            continue;
+                        }
+                        let density = self.density_at(row, col, region.height, region.width);
+                        let cell = if !rng.random_bool(density) {
+                            Cell::Dead
+                        } else if species > 1 {
+                            Cell::Species(rng.random_range(1..=species))
+                        } else {
+                            Cell::Alive
+                        };
+                        for (image_row, image_col) in
+                            self.symmetry.images(row, col, region.height, region.width)
+                        {
+                            filled[image_row * region.width + image_col] = true;
+                            cell_board.set_slot(
+                                Slot(region.row + image_row, region.col + image_col),
+                                cell,
+                            );
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub mod plaintext {
+        use super::cell::{Cell, Slot};
+        use super::{CellBoard, CellBoardCreationError, FormatErrorVariant, Pattern};
+
+        pub fn parse(input: &str) -> Result<Pattern, CellBoardCreationError> {
+            let mut name = None;
+            let mut comments = Vec::new();
+            let mut rows: Vec<Vec<bool>> = Vec::new();
+            for (line_index, line) in input.lines().enumerate() {
+                let line = line.trim_end();
+                if let Some(comment) = line.strip_prefix('!') {
+                    match comment.strip_prefix("Name:") {
+                        Some(pattern_name) if name.is_none() => {
+                            name = Some(pattern_name.trim().to_owned())
+                        }
+                        _ => comments.push(comment.trim().to_owned()),
+                    }
+                    continue;
+                }
+                let row = line
+                    .chars()
+                    .enumerate()
+                    .map(|(column, c)| match c {
+                        '.' => Ok(false),
+                        'O' | '*' => Ok(true),
+                        c => Err(CellBoardCreationError::from(
+                            FormatErrorVariant::UnrecognizedCharacter(c),
+                        )
+                        .at(line_index + 1, column + 1)),
+                    })
+                    .collect::<Result<Vec<bool>, _>>()?;
+                rows.push(row);
+            }
+            let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+            let mut cell_board = CellBoard::new(rows.len(), width);
+            for (row, cells) in rows.iter().enumerate() {
+                for (col, alive) in cells.iter().enumerate() {
+                    if *alive {
+                        cell_board.set_slot(Slot(row, col), Cell::Alive);
+                    }
+                }
+            }
+            Ok(Pattern {
+                cell_board,
+                rule: None,
+                name,
+                comments,
+            })
+        }
+
+        pub fn write(cell_board: &CellBoard, name: Option<&str>) -> String {
+            let mut plaintext = String::new();
+            if let Some(name) = name {
+                plaintext += &format!("!Name: {}\n", name);
+            }
+            for row in 0..cell_board.height() {
+                for col in 0..cell_board.width() {
+                    plaintext.push(match cell_board.get_slot(Slot(row, col)) {
+                        Cell::Alive | Cell::Species(_) => 'O',
+                        Cell::Dead | Cell::Dying(_) => '.',
+                    });
+                }
+                plaintext.push('\n');
+            }
+            plaintext
+        }
+    }
+
+    pub mod life105 {
+        use super::cell::{Cell, Slot};
+        use super::rule::Rule;
+        use super::{
+            CellBoard, CellBoardCreationError, FormatErrorVariant, Pattern,
+            cell_board_from_live_cells,
+        };
+
+        fn format_error(variant: FormatErrorVariant) -> CellBoardCreationError {
+            CellBoardCreationError::from(variant)
+        }
+
+        // cell blocks are introduced by `#P x y` lines giving the position of their
+        // top left cell, `*` is alive and `.` is dead
+        pub fn parse(input: &str) -> Result<Pattern, CellBoardCreationError> {
+            let mut live_cells = Vec::new();
+            let mut rule = None;
+            let mut comments = Vec::new();
+            let mut block: Option<(i64, i64)> = None;
+            let mut block_row = 0;
+            for (line_index, line) in input.lines().enumerate() {
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                let line = line.trim();
+                let at_line = |error: CellBoardCreationError| error.at(line_index + 1, indent + 1);
+                if line.starts_with("#Life") || line.is_empty() {
+                    continue;
+                } else if let Some(comment) = line.strip_prefix("#D") {
+                    comments.push(comment.trim().to_owned());
+                } else if line.starts_with("#N") {
+                    rule = Some(Rule::conway());
+                } else if let Some(rule_string) = line.strip_prefix("#R") {
+                    rule = Some(rule_string.trim().parse::<Rule>().map_err(|error| {
+                        at_line(format_error(FormatErrorVariant::InvalidRule(error)))
+                    })?);
+                } else if let Some(position) = line.strip_prefix("#P") {
+                    let coordinates: Vec<i64> = position
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| {
+                            at_line(format_error(FormatErrorVariant::InvalidCoordinates(
+                                line.to_owned(),
+                            )))
+                        })?;
+                    let [x, y] = coordinates[..] else {
+                        return Err(at_line(format_error(
+                            FormatErrorVariant::InvalidCoordinates(line.to_owned()),
+                        )));
+                    };
+                    block = Some((y, x));
+                    block_row = 0;
+                } else if line.starts_with('#') {
+                    continue;
+                } else {
+                    let (top, left) = block.unwrap_or((0, 0));
+                    for (col, c) in line.chars().enumerate() {
+                        match c {
+                            '*' => {
+                                let cell = top
+                                    .checked_add(block_row)
+                                    .zip(left.checked_add(col as i64))
+                                    .ok_or_else(|| {
+                                        at_line(format_error(
+                                            FormatErrorVariant::InvalidCoordinates(line.to_owned()),
+                                        ))
+                                    })?;
+                                live_cells.push(cell);
+                            }
+                            '.' => {}
+                            c => {
+                                return Err(format_error(
+                                    FormatErrorVariant::UnrecognizedCharacter(c),
+                                )
+                                .at(line_index + 1, indent + col + 1));
+                            }
+                        }
+                    }
+                    block_row += 1;
+                }
+            }
+            Ok(Pattern {
+                cell_board: cell_board_from_live_cells(&live_cells)?,
+                rule,
+                name: None,
+                comments,
+            })
+        }
+
+        pub fn write(cell_board: &CellBoard, rule: Rule) -> String {
+            let mut life = format!("#Life 1.05\n#R {}\n#P 0 0\n", rule.survival_birth());
+            for row in 0..cell_board.height() {
+                let line: String = (0..cell_board.width())
+                    .map(|col| match cell_board.get_slot(Slot(row, col)) {
+                        Cell::Alive | Cell::Species(_) => '*',
+                        Cell::Dead | Cell::Dying(_) => '.',
+                    })
+                    .collect();
+                let line = line.trim_end_matches('.');
+                life += if line.is_empty() { "." } else { line };
+                life.push('\n');
+            }
+            life
+        }
+    }
+
+    pub mod life106 {
+        use super::cell::Slot;
+        use super::{
+            CellBoard, CellBoardCreationError, FormatErrorVariant, Pattern,
+            cell_board_from_live_cells,
+        };
+
+        // one `x y` pair per live cell, the board spans their bounding box
+        pub fn parse(input: &str) -> Result<Pattern, CellBoardCreationError> {
+            let mut live_cells = Vec::new();
+            let mut comments = Vec::new();
+            for (line_index, line) in input.lines().enumerate() {
+                let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                let line = line.trim();
+                if let Some(comment) = line.strip_prefix("#D") {
+                    comments.push(comment.trim().to_owned());
+                    continue;
+                }
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let invalid = || {
+                    CellBoardCreationError::from(FormatErrorVariant::InvalidCoordinates(
+                        line.to_owned(),
+                    ))
+                    .at(line_index + 1, indent + 1)
+                };
+                let coordinates: Vec<i64> = line
+                    .split_whitespace()
+                    .map(str::parse)
+                    .collect::<Result<_, _>>()
+                    .map_err(|_| invalid())?;
+                let [x, y] = coordinates[..] else {
+                    return Err(invalid());
+                };
+                live_cells.push((y, x));
+            }
+            Ok(Pattern {
+                cell_board: cell_board_from_live_cells(&live_cells)?,
+                rule: None,
+                name: None,
+                comments,
+            })
+        }
+
+        pub fn write(cell_board: &CellBoard) -> String {
+            let mut life = String::from("#Life 1.06\n");
+            for Slot(row, col) in cell_board.live_slots() {
+                life += &format!("{} {}\n", col, row);
+            }
+            life
+        }
+    }
+
+    pub mod bitboard {
+        use super::rule::Rule;
+        use super::topology::Topology;
+
+        const WORD_BITS: usize = u64::BITS as usize;
+
+        // 64 cells per word, column `col` of a row lives in bit `col % 64` of word
+        // `col / 64`; bits past the board width are always kept at zero
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        pub struct BitBoard {
+            height: usize,
+            width: usize,
+            words_per_row: usize,
+            words: Vec<u64>,
+        }
+
+        impl BitBoard {
+            pub fn new(height: usize, width: usize) -> Self {
+                let words_per_row = width.div_ceil(WORD_BITS);
+                Self {
+                    height,
+                    width,
+                    words_per_row,
+                    words: vec![0; height * words_per_row],
+                }
+            }
+
+            pub fn height(&self) -> usize {
+                self.height
+            }
+
+            pub fn width(&self) -> usize {
+                self.width
+            }
+
+            pub fn get(&self, row: usize, col: usize) -> bool {
+                assert!(row < self.height && col < self.width, "slot out of bounds");
+                self.row(row)[col / WORD_BITS] >> (col % WORD_BITS) & 1 == 1
+            }
+
+            pub fn set(&mut self, row: usize, col: usize, alive: bool) {
+                assert!(row < self.height && col < self.width, "slot out of bounds");
+                let word = &mut self.words[row * self.words_per_row + col / WORD_BITS];
+                let mask = 1 << (col % WORD_BITS);
+                if alive {
+                    *word |= mask;
+                } else {
+                    *word &= !mask;
+                }
+            }
+
+            pub fn population(&self) -> usize {
+                self.words
+                    .iter()
+                    .map(|word| word.count_ones() as usize)
+                    .sum()
+            }
+
+            fn row(&self, row: usize) -> &[u64] {
+                &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+            }
+
+            fn last_word_mask(&self) -> u64 {
+                match self.width % WORD_BITS {
+                    0 => u64::MAX,
+                    bits => (1 << bits) - 1,
+                }
+            }
+
+            // row shifted so that every column holds the state of its western
+            // neighbor, wrapping around the board edge if `wrap` is set
+            fn shift_from_west(&self, row: &[u64], wrap: bool) -> Vec<u64> {
+                let last_col = self.width - 1;
+                let wrapped = row[last_col / WORD_BITS] >> (last_col % WORD_BITS) & 1;
+                let mut shifted = Vec::with_capacity(row.len());
+                let mut carry = if wrap { wrapped } else { 0 };
+                for &word in row {
+                    shifted.push(word << 1 | carry);
+                    carry = word >> (WORD_BITS - 1);
+                }
+                if let Some(last) = shifted.last_mut() {
+                    *last &= self.last_word_mask();
+                }
+                shifted
+            }
+
+            // row shifted so that every column holds the state of its eastern
+            // neighbor, wrapping around the board edge if `wrap` is set
+            fn shift_from_east(&self, row: &[u64], wrap: bool) -> Vec<u64> {
+                let last_col = self.width - 1;
+                let wrapped = if wrap { row[0] & 1 } else { 0 };
+                let mut shifted = vec![0; row.len()];
+                for (i, &word) in row.iter().enumerate() {
+                    let carry = row.get(i + 1).map_or(0, |next| next & 1);
+                    shifted[i] = word >> 1 | carry << (WORD_BITS - 1);
+                }
+                shifted[last_col / WORD_BITS] |= wrapped << (last_col % WORD_BITS);
+                shifted
+            }
+
+            // None for the twisted topologies, which mirror rows or columns across
+            // an edge, and for Generations rules whose dying cells do not fit into a
+            // bit; those have to be stepped cell by cell
+            pub fn step(&self, rule: &Rule, topology: Topology) -> Option<Self> {
+                if !rule.is_life_like() {
+                    return None;
+                }
+                let (wrap_horizontally, wrap_vertically) = match topology {
+                    Topology::Torus => (true, true),
+                    Topology::Plane => (false, false),
+                    Topology::HorizontalCylinder => (true, false),
+                    Topology::VerticalCylinder => (false, true),
+                    Topology::KleinBottle | Topology::CrossSurface => return None,
+                };
+                let mut next = Self::new(self.height, self.width);
+                if self.height == 0 || self.width == 0 {
+                    return Some(next);
+                }
+                // for every row: the row itself and its western and eastern shifts
+                let shifted_rows: Vec<[Vec<u64>; 3]> = (0..self.height)
+                    .map(|row| {
+                        let row = self.row(row);
+                        [
+                            row.to_vec(),
+                            self.shift_from_west(row, wrap_horizontally),
+                            self.shift_from_east(row, wrap_horizontally),
+                        ]
+                    })
+                    .collect();
+                let empty_row = [
+                    vec![0; self.words_per_row],
+                    vec![0; self.words_per_row],
+                    vec![0; self.words_per_row],
+                ];
+                let birth_masks: Vec<usize> = (0..=8).filter(|count| rule.born(*count)).collect();
+                let survival_masks: Vec<usize> =
+                    (0..=8).filter(|count| rule.survives(*count)).collect();
+                let last_word_mask = self.last_word_mask();
+
+                for row in 0..self.height {
+                    let above = if row > 0 || wrap_vertically {
+                        &shifted_rows[(row + self.height - 1) % self.height]
+                    } else {
+                        &empty_row
+                    };
+                    let current = &shifted_rows[row];
+                    let below = if row + 1 < self.height || wrap_vertically {
+                        &shifted_rows[(row + 1) % self.height]
+                    } else {
+                        &empty_row
+                    };
+                    for word in 0..self.words_per_row {
+                        let neighbors = [
+                            above[0][word],
+                            above[1][word],
+                            above[2][word],
+                            current[1][word],
+                            current[2][word],
+                            below[0][word],
+                            below[1][word],
+                            below[2][word],
+                        ];
+                        // bit-sliced 4-bit counter, one lane per cell
+                        let mut count = [0u64; 4];
+                        for neighbor in neighbors {
+                            let mut carry = neighbor;
+                            for plane in count.iter_mut() {
+                                let next_carry = *plane & carry;
+                                *plane ^= carry;
+                                carry = next_carry;
+                            }
+                        }
+                        let matching = |counts: &[usize]| {
+                            counts.iter().fold(0u64, |matches, &target| {
+                                matches
+                                    | count.iter().enumerate().fold(
+                                        u64::MAX,
+                                        |equal, (bit, plane)| {
+                                            if target >> bit & 1 == 1 {
+                                                equal & plane
+                                            } else {
+                                                equal & !plane
+                                            }
+                                        },
+                                    )
+                            })
+                        };
+                        let alive = current[0][word];
+                        let mut next_word =
+                            (alive & matching(&survival_masks)) | (!alive & matching(&birth_masks));
+                        if word == self.words_per_row - 1 {
+                            next_word &= last_word_mask;
+                        }
+                        next.words[row * self.words_per_row + word] = next_word;
+                    }
+                }
+                Some(next)
+            }
+        }
+    }
+}
+
+pub mod period {
+    use crate::game::cell::Cell;
+    use std::collections::HashMap;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Cycle {
+        // generation at which the repeating state first appeared
+        pub pre_period: u64,
+        pub period: u64,
+        // (rows, cols) the pattern moved during one period
+        pub translation: (i64, i64),
+    }
+
+    impl Cycle {
+        pub fn is_still_life(&self) -> bool {
+            self.period == 1 && self.translation == (0, 0)
+        }
+
+        pub fn is_oscillator(&self) -> bool {
+            self.period > 1 && self.translation == (0, 0)
+        }
+
+        pub fn is_spaceship(&self) -> bool {
+            self.translation != (0, 0)
+        }
+    }
+
+    impl std::fmt::Display for Cycle {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            if self.is_still_life() {
+                write!(f, "still life")?;
+            } else if self.is_oscillator() {
+                write!(f, "oscillator p{}", self.period)?;
+            } else {
+                let (dy, dx) = self.translation;
+                write!(f, "spaceship p{} moving ({}, {})", self.period, dy, dx)?;
+            }
+            write!(f, " from generation {}", self.pre_period)
+        }
+    }
+
+    // remembers the hash of every observed state, normalized to the top left
+    // corner of its bounding box so that translated copies are recognized too.
+    // Only the first state that repeats a hash is kept in full, and the cycle is
+    // reported once that state comes back a period later
+    #[derive(Clone, Default)]
+    pub struct PeriodDetector {
+        // hash -> (generation, origin) of its first observation
+        seen: HashMap<u64, (u64, (i64, i64))>,
+        candidate: Option<Candidate>,
+        cycle: Option<Cycle>,
+    }
+
+    // ((row, col), state) of every cell that is not dead
+    type States<S> = Vec<((i64, i64), S)>;
+
+    // a state whose hash was seen `period` generations before it
+    #[derive(Clone)]
+    struct Candidate {
+        generation: u64,
+        period: u64,
+        origin: (i64, i64),
+        cells: States<Cell>,
+    }
+
+    impl PeriodDetector {
+        pub fn observe(
+            &mut self,
+            generation: u64,
+            live_cells: impl IntoIterator<Item = (i64, i64)>,
+        ) -> Option<Cycle> {
+            self.observe_states(
+                generation,
+                live_cells.into_iter().map(|cell| (cell, Cell::Alive)),
+            )
+        }
+
+        // like `observe` for boards whose non-dead cells are in different states
+        pub fn observe_states(
+            &mut self,
+            generation: u64,
+            cells: impl IntoIterator<Item = ((i64, i64), Cell)>,
+        ) -> Option<Cycle> {
+            if self.cycle.is_some() {
+                return self.cycle;
+            }
+            let (cells, origin) = normalized(cells);
+            if let Some(candidate) = self.candidate.take() {
+                let due = candidate.generation + candidate.period;
+                if generation < due {
+                    self.candidate = Some(candidate);
+                } else if generation == due && candidate.cells == cells {
+                    // the state the hash of the first observation promised
+                    self.cycle = Some(Cycle {
+                        pre_period: candidate.generation - candidate.period,
+                        period: candidate.period,
+                        translation: (origin.0 - candidate.origin.0, origin.1 - candidate.origin.1),
+                    });
+                    return self.cycle;
+                }
+            }
+            let hash = state_hash(&cells);
+            match self.seen.get(&hash) {
+                None => {
+                    self.seen.insert(hash, (generation, origin));
+                }
+                // a generation that is observed again after rewinding
+                Some(&(first, _)) if first >= generation => {}
+                Some(&(first, _)) => {
+                    if self.candidate.is_none() {
+                        self.candidate = Some(Candidate {
+                            generation,
+                            period: generation - first,
+                            origin,
+                            cells,
+                        });
+                    }
+                }
+            }
+            self.cycle
+        }
+
+        pub fn cycle(&self) -> Option<Cycle> {
+            self.cycle
+        }
+
+        // forgets a cycle that had not come around yet at `generation`
+        pub fn rewind(&mut self, generation: u64) {
+            if let Some(cycle) = self.cycle
+                && generation < cycle.pre_period + cycle.period
+            {
+                self.cycle = None;
+            }
+        }
+
+        pub fn reset(&mut self) {
+            self.seen.clear();
+            self.candidate = None;
+            self.cycle = None;
+        }
+    }
+
+    // hash of the pattern relative to its top left corner, and that corner
+    pub fn normalized_hash(live_cells: impl IntoIterator<Item = (i64, i64)>) -> (u64, (i64, i64)) {
+        normalized_state_hash(live_cells.into_iter().map(|cell| (cell, ())))
+    }
+
+    pub fn normalized_state_hash<S: Hash + Ord>(
+        cells: impl IntoIterator<Item = ((i64, i64), S)>,
+    ) -> (u64, (i64, i64)) {
+        let (cells, origin) = normalized(cells);
+        (state_hash(&cells), origin)
+    }
+
+    // the cells in order, relative to the top left corner of their bounding box,
+    // and that corner
+    fn normalized<S: Ord>(
+        cells: impl IntoIterator<Item = ((i64, i64), S)>,
+    ) -> (States<S>, (i64, i64)) {
+        let mut cells: States<S> = cells.into_iter().collect();
+        cells.sort_unstable();
+        let top = cells.first().map_or(0, |((row, _), _)| *row);
+        let left = cells.iter().map(|((_, col), _)| *col).min().unwrap_or(0);
+        for ((row, col), _) in &mut cells {
+            *row -= top;
+            *col -= left;
+        }
+        (cells, (top, left))
+    }
+
+    fn state_hash<S: Hash>(cells: &[((i64, i64), S)]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        cells.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+pub mod hashlife {
+    use crate::game::cell::{Cell, Slot};
+    use crate::game::rule::Rule;
+    use crate::game::{CellBoard, CellBoardCreationError};
+    use crate::sparse::BoundingBox;
+    use std::collections::HashMap;
+
+    type NodeId = usize;
+
+    const DEAD_LEAF: NodeId = 0;
+    const ALIVE_LEAF: NodeId = 1;
+    // a root of this level spans 2^62 cells, so that coordinates relative to
+    // its center and their sums stay within an i64
+    const MAX_LEVEL: u8 = 62;
+
+    // leaves are the two single cells at level 0, a node at level k covers a
+    // 2^k x 2^k square split into four level k - 1 quadrants
+    struct Node {
+        level: u8,
+        // nw, ne, sw, se
+        children: [NodeId; 4],
+        population: u64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum HashLifeError {
+        // the empty universe would not stay empty, so it cannot be represented
+        // by a finite quadtree
+        BirthOnZeroNeighbors,
+        // nodes only distinguish live and dead cells and are stepped with the 8
+        // cell neighborhood
+        UnsupportedRule,
+        // coordinates of the root node have to fit into an i64
+        UniverseTooLarge,
+        GenerationOverflow,
+    }
+
+    impl std::fmt::Display for HashLifeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::BirthOnZeroNeighbors => {
+                    write!(
+                        f,
+                        "rules with birth on 0 neighbors are not supported by HashLife"
+                    )
+                }
+                Self::UnsupportedRule => write!(
+                    f,
+                    "only Life-like rules are supported by HashLife, not Generations or Larger than Life rules"
+                ),
+                Self::UniverseTooLarge => write!(
+                    f,
+                    "the universe would grow past the 2^{} cells across that HashLife can address",
+                    MAX_LEVEL
+                ),
+                Self::GenerationOverflow => write!(f, "the generation count would overflow"),
+            }
+        }
+    }
+
+    impl std::error::Error for HashLifeError {}
+
+    pub struct HashLife {
+        rule: Rule,
+        nodes: Vec<Node>,
+        canonical: HashMap<[NodeId; 4], NodeId>,
+        empty: Vec<NodeId>,
+        // (node, log2 of the generations to advance) -> centered result
+        results: HashMap<(NodeId, u8), NodeId>,
+        root: NodeId,
+        // coordinates (row, col) of the top left cell of the root node
+        origin: (i64, i64),
+        generation: u64,
+    }
+
+    impl HashLife {
+        pub fn new(rule: Rule) -> Result<Self, HashLifeError> {
+            if rule.born(0) {
+                return Err(HashLifeError::BirthOnZeroNeighbors);
+            }
+            if !rule.is_life_like() {
+                return Err(HashLifeError::UnsupportedRule);
+            }
+            let mut hash_life = Self {
+                rule,
+                nodes: vec![
+                    Node {
+                        level: 0,
+                        children: [DEAD_LEAF; 4],
+                        population: 0,
+                    },
+                    Node {
+                        level: 0,
+                        children: [DEAD_LEAF; 4],
+                        population: 1,
+                    },
+                ],
+                canonical: HashMap::new(),
+                empty: vec![DEAD_LEAF],
+                results: HashMap::new(),
+                root: DEAD_LEAF,
+                origin: (0, 0),
+                generation: 0,
+            };
+            hash_life.root = hash_life.empty_node(3);
+            Ok(hash_life)
+        }
+
+        pub fn from_cell_board(cell_board: &CellBoard, rule: Rule) -> Result<Self, HashLifeError> {
+            let mut hash_life = Self::new(rule)?;
+            let size = cell_board.height().max(cell_board.width()).max(8);
+            let level = size.next_power_of_two().trailing_zeros() as u8;
+            hash_life.root = hash_life.build(cell_board, level, 0, 0);
+            Ok(hash_life)
+        }
+
+        fn build(&mut self, cell_board: &CellBoard, level: u8, row: usize, col: usize) -> NodeId {
+            if row >= cell_board.height() || col >= cell_board.width() {
+                return self.empty_node(level);
+            }
+            if level == 0 {
+                return match cell_board.get_slot(Slot(row, col)) {
+                    Cell::Dead => DEAD_LEAF,
+                    _ => ALIVE_LEAF,
+                };
+            }
+            let half = 1 << (level - 1);
+            let nw = self.build(cell_board, level - 1, row, col);
+            let ne = self.build(cell_board, level - 1, row, col + half);
+            let sw = self.build(cell_board, level - 1, row + half, col);
+            let se = self.build(cell_board, level - 1, row + half, col + half);
+            self.join([nw, ne, sw, se])
+        }
+
+        pub fn rule(&self) -> Rule {
+            self.rule
+        }
+
+        pub fn generation(&self) -> u64 {
+            self.generation
+        }
+
+        pub fn set_generation(&mut self, generation: u64) {
+            self.generation = generation;
+        }
+
+        pub fn population(&self) -> u64 {
+            self.nodes[self.root].population
+        }
+
+        pub fn from_macrocell(input: &str) -> Result<Self, CellBoardCreationError> {
+            macrocell::parse(input)
+        }
+
+        pub fn to_macrocell(&self) -> String {
+            macrocell::write(self)
+        }
+
+        pub fn bounding_box(&self) -> Option<BoundingBox> {
+            let mut bounds = HashMap::new();
+            let (top, left, bottom, right) = self.node_bounds(self.root, &mut bounds)?;
+            let (row, col) = self.origin;
+            Some(BoundingBox {
+                top: row + top as i64,
+                left: col + left as i64,
+                bottom: row + bottom as i64,
+                right: col + right as i64,
+            })
+        }
+
+        // (top, left, bottom, right) of the live cells relative to the top left
+        // corner of `node`, memoized per node since subtrees are shared
+        fn node_bounds(
+            &self,
+            node: NodeId,
+            bounds: &mut HashMap<NodeId, Option<(u64, u64, u64, u64)>>,
+        ) -> Option<(u64, u64, u64, u64)> {
+            let Node {
+                level,
+                children,
+                population,
+            } = self.nodes[node];
+            if population == 0 {
+                return None;
+            }
+            if level == 0 {
+                return Some((0, 0, 0, 0));
+            }
+            if let Some(&node_bounds) = bounds.get(&node) {
+                return node_bounds;
+            }
+            let half = 1u64 << (level - 1);
+            let node_bounds = children
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, child)| {
+                    let (row, col) = ((i / 2) as u64 * half, (i % 2) as u64 * half);
+                    let (top, left, bottom, right) = self.node_bounds(child, bounds)?;
+                    Some((row + top, col + left, row + bottom, col + right))
+                })
+                .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)));
+            bounds.insert(node, node_bounds);
+            node_bounds
+        }
+
+        // on an error the universe is left at the largest size it reached
+        pub fn set_cell(&mut self, row: i64, col: i64, alive: bool) -> Result<(), HashLifeError> {
+            loop {
+                let size = 1i64 << self.nodes[self.root].level;
+                let (top, left) = self.origin;
+                if (top..top + size).contains(&row) && (left..left + size).contains(&col) {
+                    break;
+                }
+                if self.nodes[self.root].level >= MAX_LEVEL {
+                    return Err(HashLifeError::UniverseTooLarge);
+                }
+                self.expand();
+            }
+            let (top, left) = self.origin;
+            self.root = self.set_in(self.root, (row - top) as u64, (col - left) as u64, alive);
+            Ok(())
+        }
+
+        fn set_in(&mut self, node: NodeId, row: u64, col: u64, alive: bool) -> NodeId {
+            let level = self.nodes[node].level;
+            if level == 0 {
+                return if alive { ALIVE_LEAF } else { DEAD_LEAF };
+            }
+            let half = 1 << (level - 1);
+            let quadrant = (row >= half) as usize * 2 + (col >= half) as usize;
+            let mut children = self.nodes[node].children;
+            children[quadrant] = self.set_in(children[quadrant], row % half, col % half, alive);
+            self.join(children)
+        }
+
+        pub fn get_cell(&self, row: i64, col: i64) -> bool {
+            let (top, left) = self.origin;
+            let (mut row, mut col) = (row - top, col - left);
+            let mut node = self.root;
+            let size = 1i64 << self.nodes[node].level;
+            if !(0..size).contains(&row) || !(0..size).contains(&col) {
+                return false;
+            }
+            while self.nodes[node].level > 0 {
+                let half = 1i64 << (self.nodes[node].level - 1);
+                let quadrant = (row >= half) as usize * 2 + (col >= half) as usize;
+                node = self.nodes[node].children[quadrant];
+                row %= half;
+                col %= half;
+            }
+            node == ALIVE_LEAF
+        }
+
+        // live cells as (row, col), relative to the top left cell of the board the
+        // universe was loaded from
+        pub fn live_cells(&self) -> Vec<(i64, i64)> {
+            let mut cells = Vec::new();
+            self.collect_live_cells(self.root, self.origin, &mut cells);
+            cells.sort_unstable();
+            cells
+        }
+
+        fn collect_live_cells(
+            &self,
+            node: NodeId,
+            (row, col): (i64, i64),
+            cells: &mut Vec<(i64, i64)>,
+        ) {
+            let Node {
+                level,
+                children,
+                population,
+            } = self.nodes[node];
+            if population == 0 {
+                return;
+            }
+            if level == 0 {
+                cells.push((row, col));
+                return;
+            }
+            let half = 1i64 << (level - 1);
+            for (i, child) in children.into_iter().enumerate() {
+                let offset = ((i / 2) as i64 * half, (i % 2) as i64 * half);
+                self.collect_live_cells(child, (row + offset.0, col + offset.1), cells);
+            }
+        }
+
+        // cells outside of the 0..height, 0..width window are dropped
+        pub fn to_cell_board(&self, height: usize, width: usize) -> CellBoard {
+            let mut cell_board = CellBoard::new(height, width);
+            for (row, col) in self.live_cells() {
+                if (0..height as i64).contains(&row) && (0..width as i64).contains(&col) {
+                    cell_board.set_slot(Slot(row as usize, col as usize), Cell::Alive);
+                }
+            }
+            cell_board
+        }
+
+        // on an error the universe is left at the last power of two it reached
+        pub fn step(&mut self, generations: u64) -> Result<(), HashLifeError> {
+            self.generation
+                .checked_add(generations)
+                .ok_or(HashLifeError::GenerationOverflow)?;
+            for k in 0..u64::BITS {
+                if generations >> k & 1 == 1 {
+                    self.advance_pow2(k)?;
+                }
+            }
+            Ok(())
+        }
+
+        pub fn advance_pow2(&mut self, k: u32) -> Result<(), HashLifeError> {
+            if k > u32::from(MAX_LEVEL - 3) {
+                return Err(HashLifeError::UniverseTooLarge);
+            }
+            let generation = self
+                .generation
+                .checked_add(1 << k)
+                .ok_or(HashLifeError::GenerationOverflow)?;
+            let k = k as u8;
+            // the pattern must sit in the central quarter of a node of at least
+            // level k + 3 so that nothing escapes the centered result
+            while self.nodes[self.root].level < k + 3 || !self.is_centered() {
+                if self.nodes[self.root].level >= MAX_LEVEL {
+                    return Err(HashLifeError::UniverseTooLarge);
+                }
+                self.expand();
+            }
+            let level = self.nodes[self.root].level;
+            let shift = 1i64 << (level - 2);
+            self.root = self.advance(self.root, k);
+            self.origin = (self.origin.0 + shift, self.origin.1 + shift);
+            self.generation = generation;
+            // keep the root at least as large as a macrocell leaf
+            while self.nodes[self.root].level < 3 {
+                self.expand();
+            }
+            Ok(())
+        }
+
+        fn is_centered(&self) -> bool {
+            let [nw, ne, sw, se] = self.nodes[self.root].children;
+            let inner = [
+                self.nodes[self.nodes[nw].children[3]].children[3],
+                self.nodes[self.nodes[ne].children[2]].children[2],
+                self.nodes[self.nodes[sw].children[1]].children[1],
+                self.nodes[self.nodes[se].children[0]].children[0],
+            ];
+            let inner_population: u64 = inner.iter().map(|node| self.nodes[*node].population).sum();
+            inner_population == self.population()
+        }
+
+        fn expand(&mut self) {
+            let level = self.nodes[self.root].level;
+            let empty = self.empty_node(level - 1);
+            let [nw, ne, sw, se] = self.nodes[self.root].children;
+            let children = [
+                self.join([empty, empty, empty, nw]),
+                self.join([empty, empty, ne, empty]),
+                self.join([empty, sw, empty, empty]),
+                self.join([se, empty, empty, empty]),
+            ];
+            self.root = self.join(children);
+            let half = 1i64 << (level - 1);
+            self.origin = (self.origin.0 - half, self.origin.1 - half);
+        }
+
+        fn join(&mut self, children: [NodeId; 4]) -> NodeId {
+            if let Some(&node) = self.canonical.get(&children) {
+                return node;
+            }
+            let node = Node {
+                level: self.nodes[children[0]].level + 1,
+                children,
+                population: children
+                    .iter()
+                    .map(|child| self.nodes[*child].population)
+                    .sum(),
+            };
+            self.nodes.push(node);
+            let id = self.nodes.len() - 1;
+            self.canonical.insert(children, id);
+            id
+        }
+
+        fn empty_node(&mut self, level: u8) -> NodeId {
+            while self.empty.len() <= level as usize {
+                let empty = *self.empty.last().expect("level 0 is always present");
+                let node = self.join([empty; 4]);
+                self.empty.push(node);
+            }
+            self.empty[level as usize]
+        }
+
+        // the level k - 1 node centered in `node`
+        fn centered(&mut self, node: NodeId) -> NodeId {
+            let [nw, ne, sw, se] = self.nodes[node].children;
+            self.join([
+                self.nodes[nw].children[3],
+                self.nodes[ne].children[2],
+                self.nodes[sw].children[1],
+                self.nodes[se].children[0],
+            ])
+        }
+
+        // the level k - 1 node centered in `node`, 2^k_log generations later
+        fn advance(&mut self, node: NodeId, k_log: u8) -> NodeId {
+            let Node {
+                level, population, ..
+            } = self.nodes[node];
+            if population == 0 {
+                return self.empty_node(level - 1);
+            }
+            if let Some(&result) = self.results.get(&(node, k_log)) {
+                return result;
+            }
+            let result = if level == 2 {
+                self.advance_leaf_square(node)
+            } else {
+                let [nw, ne, sw, se] = self.nodes[node].children;
+                let [_, nw_ne, nw_sw, nw_se] = self.nodes[nw].children;
+                let [ne_nw, _, ne_sw, ne_se] = self.nodes[ne].children;
+                let [sw_nw, sw_ne, _, sw_se] = self.nodes[sw].children;
+                let [se_nw, se_ne, se_sw, _] = self.nodes[se].children;
+                let overlapping = [
+                    nw,
+                    self.join([nw_ne, ne_nw, nw_se, ne_sw]),
+                    ne,
+                    self.join([nw_sw, nw_se, sw_nw, sw_ne]),
+                    self.join([nw_se, ne_sw, sw_ne, se_nw]),
+                    self.join([ne_sw, ne_se, se_nw, se_ne]),
+                    sw,
+                    self.join([sw_ne, se_nw, sw_se, se_sw]),
+                    se,
+                ];
+                // at full speed both halves of the jump advance, otherwise the
+                // first half only recenters
+                let full_speed = k_log == level - 2;
+                let partial: Vec<NodeId> = overlapping
+                    .into_iter()
+                    .map(|node| {
+                        if full_speed {
+                            self.advance(node, k_log - 1)
+                        } else {
+                            self.centered(node)
+                        }
+                    })
+                    .collect();
+                let second_k_log = k_log.min(level - 3);
+                let mut quadrants = [DEAD_LEAF; 4];
+                for (i, quadrant) in quadrants.iter_mut().enumerate() {
+                    let (row, col) = (i / 2, i % 2);
+                    let combined = self.join([
+                        partial[row * 3 + col],
+                        partial[row * 3 + col + 1],
+                        partial[(row + 1) * 3 + col],
+                        partial[(row + 1) * 3 + col + 1],
+                    ]);
+                    *quadrant = self.advance(combined, second_k_log);
+                }
+                self.join(quadrants)
+            };
+            self.results.insert((node, k_log), result);
+            result
+        }
+
+        // one generation of the central 2x2 cells of a 4x4 node
+        fn advance_leaf_square(&mut self, node: NodeId) -> NodeId {
+            let mut cells = [[false; 4]; 4];
+            for (i, child) in self.nodes[node].children.into_iter().enumerate() {
+                for (j, leaf) in self.nodes[child].children.into_iter().enumerate() {
+                    cells[(i / 2) * 2 + j / 2][(i % 2) * 2 + j % 2] = leaf == ALIVE_LEAF;
+                }
+            }
+            let mut next = [DEAD_LEAF; 4];
+            for (i, leaf) in next.iter_mut().enumerate() {
+                let (row, col) = (1 + i / 2, 1 + i % 2);
+                let mut live_neighbors = 0;
+                for dy in 0..3 {
+                    for dx in 0..3 {
+                        if (dy, dx) != (1, 1) && cells[row + dy - 1][col + dx - 1] {
+                            live_neighbors += 1;
+                        }
+                    }
+                }
+                let alive = if cells[row][col] {
+                    self.rule.survives(live_neighbors)
+                } else {
+                    self.rule.born(live_neighbors)
+                };
+                *leaf = if alive { ALIVE_LEAF } else { DEAD_LEAF };
+            }
+            self.join(next)
+        }
+    }
+    // Golly's macrocell format: every line after the header describes one node of
+    // the quadtree, either an 8x8 leaf drawn with `.`, `*` and `$`, or
+    // `level nw ne sw se` referring to earlier lines by their 1-based index, with
+    // 0 standing for an empty node; the last node is the root
+    pub mod macrocell {
+        use super::{HashLife, Node, NodeId};
+        use crate::game::rule::Rule;
+        use crate::game::{CellBoardCreationError, FormatErrorVariant};
+        use std::collections::HashMap;
+
+        const LEAF_LEVEL: u8 = 3;
+        const LEAF_SIZE: usize = 1 << LEAF_LEVEL;
+
+        fn format_error(variant: FormatErrorVariant) -> CellBoardCreationError {
+            CellBoardCreationError::from(variant)
+        }
+
+        pub fn parse(input: &str) -> Result<HashLife, CellBoardCreationError> {
+            // 1-based line number, indentation and trimmed content of each line
+            let mut lines = input
+                .lines()
+                .enumerate()
+                .map(|(line_index, line)| {
+                    let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+                    (line_index + 1, indent, line.trim())
+                })
+                .filter(|(_, _, line)| !line.is_empty());
+            match lines.next() {
+                Some((_, _, header)) if header.starts_with("[M2]") => {}
+                Some((line_number, indent, header)) => {
+                    return Err(
+                        format_error(FormatErrorVariant::InvalidHeader(header.to_owned()))
+                            .at(line_number, indent + 1),
+                    );
+                }
+                None => {
+                    return Err(format_error(FormatErrorVariant::InvalidHeader(
+                        String::new(),
+                    )));
+                }
+            }
+            let mut rule = Rule::default();
+            let mut generation = 0;
+            let mut node_lines = Vec::new();
+            for (line_number, indent, line) in lines {
+                if let Some(rule_string) = line.strip_prefix("#R") {
+                    rule = rule_string.trim().parse().map_err(|error| {
+                        format_error(FormatErrorVariant::InvalidRule(error))
+                            .at(line_number, indent + 1)
+                    })?;
+                } else if let Some(generation_string) = line.strip_prefix("#G") {
+                    generation = generation_string.trim().parse().map_err(|_| {
+                        format_error(FormatErrorVariant::InvalidHeader(line.to_owned()))
+                            .at(line_number, indent + 1)
+                    })?;
+                } else if !line.starts_with('#') {
+                    node_lines.push((line_number, indent, line));
+                }
+            }
+
+            let mut hash_life = HashLife::new(rule)
+                .map_err(|_| format_error(FormatErrorVariant::UnsupportedRule(Box::new(rule))))?;
+            hash_life.generation = generation;
+            // index 0 is the empty node, whose level depends on where it is used
+            let mut nodes: Vec<Option<NodeId>> = vec![None];
+            for (line_number, indent, line) in node_lines {
+                let invalid = || {
+                    format_error(FormatErrorVariant::InvalidNode(line.to_owned()))
+                        .at(line_number, indent + 1)
+                };
+                let node = if line.starts_with(['.', '*', '$']) {
+                    parse_leaf(&mut hash_life, line).map_err(|(column, variant)| {
+                        format_error(variant).at(line_number, indent + column + 1)
+                    })?
+                } else {
+                    let numbers: Vec<usize> = line
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| invalid())?;
+                    let [level, nw, ne, sw, se] = numbers[..] else {
+                        return Err(invalid());
+                    };
+                    if level <= LEAF_LEVEL as usize || level >= 63 {
+                        return Err(invalid());
+                    }
+                    let level = level as u8;
+                    let mut children = [0; 4];
+                    for (child, index) in children.iter_mut().zip([nw, ne, sw, se]) {
+                        *child = match nodes.get(index).ok_or_else(invalid)? {
+                            None => hash_life.empty_node(level - 1),
+                            Some(node) if hash_life.nodes[*node].level == level - 1 => *node,
+                            Some(_) => return Err(invalid()),
+                        };
+                    }
+                    hash_life.join(children)
+                };
+                nodes.push(Some(node));
+            }
+            if let Some(&Some(root)) = nodes.last() {
+                let half = 1i64 << (hash_life.nodes[root].level - 1);
+                hash_life.root = root;
+                hash_life.origin = (-half, -half);
+            }
+            Ok(hash_life)
+        }
+
+        // errors carry the 0-based column of the offending character
+        fn parse_leaf(
+            hash_life: &mut HashLife,
+            line: &str,
+        ) -> Result<NodeId, (usize, FormatErrorVariant)> {
+            let mut cells = [[false; LEAF_SIZE]; LEAF_SIZE];
+            let (mut row, mut col) = (0, 0);
+            for (column, c) in line.chars().enumerate() {
+                match c {
+                    '$' => {
+                        row += 1;
+                        col = 0;
+                        continue;
+                    }
+                    '.' | '*' if row < LEAF_SIZE && col < LEAF_SIZE => cells[row][col] = c == '*',
+                    '.' | '*' => {
+                        return Err((column, FormatErrorVariant::InvalidNode(line.to_owned())));
+                    }
+                    c => return Err((column, FormatErrorVariant::UnrecognizedCharacter(c))),
+                }
+                col += 1;
+            }
+            Ok(build_leaf(hash_life, &cells, LEAF_LEVEL, 0, 0))
+        }
+
+        fn build_leaf(
+            hash_life: &mut HashLife,
+            cells: &[[bool; LEAF_SIZE]; LEAF_SIZE],
+            level: u8,
+            row: usize,
+            col: usize,
+        ) -> NodeId {
+            if level == 0 {
+                return if cells[row][col] {
+                    super::ALIVE_LEAF
+                } else {
+                    super::DEAD_LEAF
+                };
+            }
+            let half = 1 << (level - 1);
+            let children = [
+                build_leaf(hash_life, cells, level - 1, row, col),
+                build_leaf(hash_life, cells, level - 1, row, col + half),
+                build_leaf(hash_life, cells, level - 1, row + half, col),
+                build_leaf(hash_life, cells, level - 1, row + half, col + half),
+            ];
+            hash_life.join(children)
+        }
+
+        fn leaf_cell(
+            hash_life: &HashLife,
+            mut node: NodeId,
+            mut row: usize,
+            mut col: usize,
+        ) -> bool {
+            while hash_life.nodes[node].level > 0 {
+                let half = 1 << (hash_life.nodes[node].level - 1);
+                node = hash_life.nodes[node].children
+                    [(row >= half) as usize * 2 + (col >= half) as usize];
+                row %= half;
+                col %= half;
+            }
+            node == super::ALIVE_LEAF
+        }
+
+        pub fn write(hash_life: &HashLife) -> String {
+            let mut macrocell = format!("[M2] (game-of-life)\n#R {}\n", hash_life.rule);
+            if hash_life.generation > 0 {
+                macrocell += &format!("#G {}\n", hash_life.generation);
+            }
+            let mut indices = HashMap::new();
+            write_node(hash_life, hash_life.root, &mut indices, &mut macrocell);
+            macrocell
+        }
+
+        // writes the subtree below `node` after its children and returns its line
+        // index, 0 for empty nodes
+        fn write_node(
+            hash_life: &HashLife,
+            node: NodeId,
+            indices: &mut HashMap<NodeId, usize>,
+            macrocell: &mut String,
+        ) -> usize {
+            let Node {
+                level,
+                children,
+                population,
+            } = hash_life.nodes[node];
+            if population == 0 {
+                return 0;
+            }
+            if let Some(&index) = indices.get(&node) {
+                return index;
+            }
+            if level == LEAF_LEVEL {
+                for row in 0..LEAF_SIZE {
+                    let line: String = (0..LEAF_SIZE)
+                        .map(|col| {
+                            if leaf_cell(hash_life, node, row, col) {
+                                '*'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect();
+                    macrocell.push_str(line.trim_end_matches('.'));
+                    macrocell.push('$');
+                }
+                macrocell.push('\n');
+            } else {
+                let children =
+                    children.map(|child| write_node(hash_life, child, indices, macrocell));
+                macrocell.push_str(&format!(
+                    "{} {} {} {} {}\n",
+                    level, children[0], children[1], children[2], children[3]
+                ));
+            }
+            let index = indices.len() + 1;
+            indices.insert(node, index);
+            index
+        }
+    }
+}
+
+pub mod sparse {
+    use crate::game::cell::{Cell, Slot};
+    use crate::game::rule::Rule;
+    use crate::game::{CellBoard, CellBoardCreationError, FormatErrorVariant, check_board_size};
+    use crate::hashlife::{HashLife, HashLifeError};
+    use crate::period::{Cycle, PeriodDetector};
+    use std::collections::{HashMap, HashSet};
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    // the default hasher is slow for the small integer positions that are hashed
+    // several times per live cell and generation, a multiply and rotate is enough
+    #[derive(Default)]
+    struct PositionHasher(u64);
+
+    impl Hasher for PositionHasher {
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.write_u64(byte as u64);
+            }
+        }
+
+        fn write_u64(&mut self, n: u64) {
+            self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+        }
+
+        fn write_i64(&mut self, n: i64) {
+            self.write_u64(n as u64);
+        }
+
+        fn finish(&self) -> u64 {
+            self.0
+        }
+    }
+
+    type PositionSet = HashSet<(i64, i64), BuildHasherDefault<PositionHasher>>;
+    type PositionMap<V> = HashMap<(i64, i64), V, BuildHasherDefault<PositionHasher>>;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BoundingBox {
+        pub top: i64,
+        pub left: i64,
+        pub bottom: i64,
+        pub right: i64,
+    }
+
+    impl BoundingBox {
+        // a box spanning every i64 is one cell more than a u64 holds
+        pub fn height(&self) -> u64 {
+            self.bottom.abs_diff(self.top).saturating_add(1)
+        }
+
+        pub fn width(&self) -> u64 {
+            self.right.abs_diff(self.left).saturating_add(1)
+        }
+
+        pub fn contains(&self, (row, col): (i64, i64)) -> bool {
+            (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SparseUniverseError {
+        // every empty cell would be born, filling the infinite plane
+        BirthOnZeroNeighbors,
+        // only the positions of live cells are stored, and only the 8 cell
+        // neighborhood is looked at
+        UnsupportedRule,
+    }
+
+    impl std::fmt::Display for SparseUniverseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::BirthOnZeroNeighbors => write!(
+                    f,
+                    "rules with birth on 0 neighbors are not supported in an unbounded universe"
+                ),
+                Self::UnsupportedRule => write!(
+                    f,
+                    "only Life-like rules are supported in an unbounded universe, not Generations or Larger than Life rules"
+                ),
+            }
+        }
+    }
+
+    impl std::error::Error for SparseUniverseError {}
+
+    // unbounded plane that only stores the (row, col) positions of live cells
+    #[derive(Clone)]
+    pub struct SparseUniverse {
+        rule: Rule,
+        live_cells: PositionSet,
+        generation: u64,
+        period_detector: Option<PeriodDetector>,
+    }
+
+    impl SparseUniverse {
+        pub fn new(rule: Rule) -> Result<Self, SparseUniverseError> {
+            if rule.born(0) {
+                return Err(SparseUniverseError::BirthOnZeroNeighbors);
+            }
+            if !rule.is_life_like() {
+                return Err(SparseUniverseError::UnsupportedRule);
+            }
+            Ok(Self {
+                rule,
+                live_cells: PositionSet::default(),
+                generation: 0,
+                period_detector: None,
+            })
+        }
+
+        pub fn from_cell_board(
+            cell_board: &CellBoard,
+            rule: Rule,
+        ) -> Result<Self, SparseUniverseError> {
+            let mut universe = Self::new(rule)?;
+            for row in 0..cell_board.height() {
+                for col in 0..cell_board.width() {
+                    if cell_board.get_slot(Slot(row, col)).is_alive() {
+                        universe.set_cell(row as i64, col as i64, true);
+                    }
+                }
+            }
+            Ok(universe)
+        }
+
+        pub fn with_period_detection(mut self) -> Self {
+            self.period_detector = Some(PeriodDetector::default());
+            self.observe_period();
+            self
+        }
+
+        fn observe_period(&mut self) {
+            if let Some(period_detector) = &mut self.period_detector {
+                period_detector.observe(self.generation, self.live_cells.iter().copied());
+            }
+        }
+
+        pub fn cycle(&self) -> Option<Cycle> {
+            self.period_detector.as_ref()?.cycle()
+        }
+
+        pub fn from_macrocell(input: &str) -> Result<Self, CellBoardCreationError> {
+            let hash_life = HashLife::from_macrocell(input)?;
+            let mut universe = Self::new(hash_life.rule()).map_err(|_| {
+                CellBoardCreationError::from(FormatErrorVariant::UnsupportedRule(Box::new(
+                    hash_life.rule(),
+                )))
+            })?;
+            universe.live_cells.extend(hash_life.live_cells());
+            Ok(universe)
+        }
+
+        pub fn to_macrocell(&self) -> Result<String, HashLifeError> {
+            let mut hash_life =
+                HashLife::new(self.rule).expect("rules with birth on 0 are rejected by new");
+            for &(row, col) in &self.live_cells {
+                hash_life.set_cell(row, col, true)?;
+            }
+            Ok(hash_life.to_macrocell())
+        }
+
+        pub fn rule(&self) -> Rule {
+            self.rule
+        }
+
+        pub fn generation(&self) -> u64 {
+            self.generation
+        }
+
+        pub fn population(&self) -> usize {
+            self.live_cells.len()
+        }
+
+        pub fn get_cell(&self, row: i64, col: i64) -> bool {
+            self.live_cells.contains(&(row, col))
+        }
+
+        pub fn set_cell(&mut self, row: i64, col: i64, alive: bool) {
+            if alive {
+                self.live_cells.insert((row, col));
+            } else {
+                self.live_cells.remove(&(row, col));
+            }
+            if let Some(period_detector) = &mut self.period_detector {
+                period_detector.reset();
+                self.observe_period();
+            }
+        }
+
+        pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> {
+            self.live_cells.iter().copied()
+        }
+
+        pub fn bounding_box(&self) -> Option<BoundingBox> {
+            let mut cells = self.live_cells.iter();
+            let &(row, col) = cells.next()?;
+            let mut bounding_box = BoundingBox {
+                top: row,
+                left: col,
+                bottom: row,
+                right: col,
+            };
+            for &(row, col) in cells {
+                bounding_box.top = bounding_box.top.min(row);
+                bounding_box.bottom = bounding_box.bottom.max(row);
+                bounding_box.left = bounding_box.left.min(col);
+                bounding_box.right = bounding_box.right.max(col);
+            }
+            Some(bounding_box)
+        }
+
+        // the bounding box of the pattern as a fixed board, together with the
+        // position of its top left cell; an empty universe gives an empty board,
+        // a box too large to hold is refused
+        pub fn to_cell_board(&self) -> Result<(CellBoard, (i64, i64)), CellBoardCreationError> {
+            let Some(bounding_box) = self.bounding_box() else {
+                return Ok((CellBoard::new(0, 0), (0, 0)));
+            };
+            let (height, width) = (bounding_box.height(), bounding_box.width());
+            check_board_size(height, width)?;
+            let mut cell_board = CellBoard::new(height as usize, width as usize);
+            for &(row, col) in &self.live_cells {
+                cell_board.set_slot(
+                    Slot(
+                        (row - bounding_box.top) as usize,
+                        (col - bounding_box.left) as usize,
+                    ),
+                    Cell::Alive,
+                );
+            }
+            Ok((cell_board, (bounding_box.top, bounding_box.left)))
+        }
+
+        pub fn tick(&mut self) {
+            let mut live_neighbors: PositionMap<usize> = PositionMap::with_capacity_and_hasher(
+                self.live_cells.len() * 4,
+                Default::default(),
+            );
+            for &(row, col) in &self.live_cells {
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if (dy, dx) != (0, 0) {
+                            *live_neighbors.entry((row + dy, col + dx)).or_default() += 1;
+                        }
+                    }
+                }
+            }
+            let mut next: PositionSet = live_neighbors
+                .iter()
+                .filter(|(position, count)| {
+                    if self.live_cells.contains(position) {
+                        self.rule.survives(**count)
+                    } else {
+                        self.rule.born(**count)
+                    }
+                })
+                .map(|(position, _)| *position)
+                .collect();
+            if self.rule.survives(0) {
+                next.extend(
+                    self.live_cells
+                        .iter()
+                        .filter(|position| !live_neighbors.contains_key(position)),
+                );
+            }
+            self.live_cells = next;
+            self.generation += 1;
+            self.observe_period();
+        }
+
+        pub fn step(&mut self, generations: u64) {
+            for _ in 0..generations {
+                self.tick();
+            }
+        }
+    }
+}
+
+pub mod apgcode {
+    use crate::game::CellBoard;
+    use crate::game::cell::{Cell, Slot};
+    use crate::game::rule::Rule;
+    use crate::objects;
+    use crate::period::Cycle;
+    use crate::sparse::SparseUniverseError;
+    use std::collections::HashSet;
+
+    const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ApgcodeError {
+        // not xs, xp or xq followed by a number and an underscore
+        InvalidPrefix(String),
+        InvalidCharacter(char),
+        // a y at the end without the length of its run of blank columns
+        UnfinishedRun,
+        UnsupportedRule(SparseUniverseError),
+        // the pattern changes before it repeats, or does not repeat within
+        // objects::MAX_PERIOD generations
+        NotPeriodic,
+    }
+
+    impl std::fmt::Display for ApgcodeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::InvalidPrefix(prefix) => write!(
+                    f,
+                    "invalid apgcode prefix '{}', expected xs, xp or xq followed by a number and '_'",
+                    prefix
+                ),
+                Self::InvalidCharacter(character) => {
+                    write!(f, "invalid character '{}' in apgcode", character)
+                }
+                Self::UnfinishedRun => write!(f, "apgcode ends in 'y' without a run length"),
+                Self::UnsupportedRule(error) => write!(f, "{}", error),
+                Self::NotPeriodic => write!(
+                    f,
+                    "only still lifes, oscillators and spaceships with a period of at most {} have an apgcode",
+                    objects::MAX_PERIOD
+                ),
+            }
+        }
+    }
+
+    impl std::error::Error for ApgcodeError {}
+
+    // the apgcode of the live cells of the board under the rule
+    pub fn encode(cell_board: &CellBoard, rule: Rule) -> Result<String, ApgcodeError> {
+        let cells: Vec<(i64, i64)> = cell_board
+            .slots_and_cells()
+            .filter(|(_, cell)| cell.is_alive())
+            .map(|(Slot(row, col), _)| (row as i64, col as i64))
+            .collect();
+        encode_cells(rule, &cells)
+    }
+
+    // the apgcode of the cells simulated on their own on an unbounded plane
+    pub fn encode_cells(rule: Rule, cells: &[(i64, i64)]) -> Result<String, ApgcodeError> {
+        let periodic = objects::periodic(rule, cells)
+            .map_err(ApgcodeError::UnsupportedRule)?
+            .ok_or(ApgcodeError::NotPeriodic)?;
+        Ok(apgcode(&periodic.cycle, &periodic.phases))
+    }
+
+    // the board just large enough for the pattern an apgcode names, in the
+    // orientation and phase of its encoding
+    pub fn decode(apgcode: &str) -> Result<CellBoard, ApgcodeError> {
+        let apgcode = apgcode.trim();
+        let (prefix, encoding) = apgcode
+            .split_once('_')
+            .ok_or_else(|| ApgcodeError::InvalidPrefix(apgcode.to_owned()))?;
+        let valid_prefix = ObjectKind::of(prefix).is_some()
+            && prefix.len() > 2
+            && prefix[2..].bytes().all(|byte| byte.is_ascii_digit());
+        if !valid_prefix {
+            return Err(ApgcodeError::InvalidPrefix(prefix.to_owned()));
+        }
+
+        let cells = decode_wechsler(encoding)?;
+        let height = cells.iter().map(|&(row, _)| row + 1).max().unwrap_or(1);
+        let width = cells.iter().map(|&(_, col)| col + 1).max().unwrap_or(1);
+        let mut cell_board = CellBoard::new(height, width);
+        for (row, col) in cells {
+            cell_board.set_slot(Slot(row, col), Cell::Alive);
+        }
+        Ok(cell_board)
+    }
+
+    fn decode_wechsler(encoding: &str) -> Result<Vec<(usize, usize)>, ApgcodeError> {
+        let digit = |character: char| {
+            DIGITS
+                .iter()
+                .position(|&digit| digit as char == character)
+                .ok_or(ApgcodeError::InvalidCharacter(character))
+        };
+        let mut cells = Vec::new();
+        let (mut strip_top, mut col) = (0, 0);
+        let mut characters = encoding.chars();
+        while let Some(character) = characters.next() {
+            match character {
+                'w' => col += 2,
+                'x' => col += 3,
+                'y' => col += 4 + digit(characters.next().ok_or(ApgcodeError::UnfinishedRun)?)?,
+                'z' => {
+                    strip_top += 5;
+                    col = 0;
+                }
+                _ => {
+                    let column = digit(character)?;
+                    cells.extend(
+                        (0..5)
+                            .filter(|bit| column & 1 << bit != 0)
+                            .map(|bit| (strip_top + bit, col)),
+                    );
+                    col += 1;
+                }
+            }
+        }
+        Ok(cells)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ObjectKind {
+        StillLife,
+        Oscillator,
+        Spaceship,
+    }
+
+    impl ObjectKind {
+        // the kind of object an apgcode names, from its prefix
+        pub fn of(apgcode: &str) -> Option<Self> {
+            match apgcode.get(..2)? {
+                "xs" => Some(ObjectKind::StillLife),
+                "xp" => Some(ObjectKind::Oscillator),
+                "xq" => Some(ObjectKind::Spaceship),
+                _ => None,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            match self {
+                ObjectKind::StillLife => "still life",
+                ObjectKind::Oscillator => "oscillator",
+                ObjectKind::Spaceship => "spaceship",
+            }
+        }
+    }
+
+    impl std::fmt::Display for ObjectKind {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.name())
+        }
+    }
+
+    // the apgcode of an object given its cycle and the live cells of each of its
+    // phases: xs with the population for still lifes, xp or xq with the period
+    // for oscillators and spaceships, then the canonical Wechsler encoding
+    pub fn apgcode(cycle: &Cycle, phases: &[Vec<(i64, i64)>]) -> String {
+        let prefix = if cycle.is_still_life() {
+            format!("xs{}", phases.first().map_or(0, Vec::len))
+        } else if cycle.is_oscillator() {
+            format!("xp{}", cycle.period)
+        } else {
+            format!("xq{}", cycle.period)
+        };
+        format!("{}_{}", prefix, canonical_wechsler(phases))
+    }
+
+    // the shortest, then alphabetically first, Wechsler encoding of any of the
+    // phases in any of the 8 orientations
+    pub fn canonical_wechsler(phases: &[Vec<(i64, i64)>]) -> String {
+        phases
+            .iter()
+            .flat_map(|cells| {
+                (0..8).map(move |orientation| {
+                    let oriented: Vec<(i64, i64)> = cells
+                        .iter()
+                        .map(|&cell| orient(cell, orientation))
+                        .collect();
+                    wechsler(&oriented)
+                })
+            })
+            .min_by(|lhs, rhs| lhs.len().cmp(&rhs.len()).then_with(|| lhs.cmp(rhs)))
+            .unwrap_or_default()
+    }
+
+    // one of the 8 rotations and reflections of the square: bit 0 flips the rows,
+    // bit 1 the columns and bit 2 swaps rows and columns
+    fn orient((row, col): (i64, i64), orientation: u8) -> (i64, i64) {
+        let (row, col) = if orientation & 4 != 0 {
+            (col, row)
+        } else {
+            (row, col)
+        };
+        (
+            if orientation & 1 != 0 { -row } else { row },
+            if orientation & 2 != 0 { -col } else { col },
+        )
+    }
+
+    // extended Wechsler format of the cells relative to their bounding box: strips
+    // of 5 rows separated by z, each column of a strip a digit from 0 to v with
+    // the top row as its lowest bit, runs of blank columns shortened to w (2), x
+    // (3) or y and a digit (4 to 39) and blank columns at the end of a strip left
+    // out; 0 for no cells
+    pub fn wechsler(cells: &[(i64, i64)]) -> String {
+        let live: HashSet<(i64, i64)> = cells.iter().copied().collect();
+        let (Some(top), Some(bottom)) = (
+            cells.iter().map(|&(row, _)| row).min(),
+            cells.iter().map(|&(row, _)| row).max(),
+        ) else {
+            return String::from("0");
+        };
+        let left = cells.iter().map(|&(_, col)| col).min().unwrap_or(0);
+        let right = cells.iter().map(|&(_, col)| col).max().unwrap_or(0);
+
+        let mut strips = Vec::new();
+        for strip_top in (top..=bottom).step_by(5) {
+            let mut strip = String::new();
+            let mut blank_columns = 0;
+            for col in left..=right {
+                let column = (0..5)
+                    .filter(|bit| live.contains(&(strip_top + bit, col)))
+                    .fold(0, |column, bit| column | 1 << bit);
+                if column == 0 {
+                    blank_columns += 1;
+                    continue;
+                }
+                push_blank_columns(&mut strip, blank_columns);
+                blank_columns = 0;
+                strip.push(DIGITS[column] as char);
+            }
+            strips.push(strip);
+        }
+        strips.join("z")
+    }
+
+    fn push_blank_columns(strip: &mut String, mut blank_columns: usize) {
+        while blank_columns > 0 {
+            let run = blank_columns.min(39);
+            match run {
+                1 => strip.push('0'),
+                2 => strip.push('w'),
+                3 => strip.push('x'),
+                _ => {
+                    strip.push('y');
+                    strip.push(DIGITS[run - 4] as char);
+                }
+            }
+            blank_columns -= run;
+        }
+    }
+}
+
+pub mod census {
+    use crate::game::Game;
+    use crate::game::rule::Rule;
+    use crate::game::soup::{Soup, SoupError};
+    use crate::objects;
+    use crate::sparse::{SparseUniverse, SparseUniverseError};
+    use std::collections::HashMap;
+
+    // a soup counts as stable once its population repeats with a period of at
+    // most MAX_STABLE_PERIOD for STABLE_WINDOW generations, checked every
+    // CHECK_INTERVAL generations
+    const MAX_STABLE_PERIOD: usize = 120;
+    const STABLE_WINDOW: usize = 240;
+    const CHECK_INTERVAL: u64 = 60;
+    // soups growing past this many live cells per cell of the soup are explosive
+    const MAX_GROWTH: usize = 64;
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum CensusError {
+        Rule(SparseUniverseError),
+        Soup(SoupError),
+    }
+
+    impl std::fmt::Display for CensusError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Rule(error) => write!(f, "{}", error),
+                Self::Soup(error) => write!(f, "{}", error),
+            }
+        }
+    }
+
+    impl std::error::Error for CensusError {}
+
+    impl From<SparseUniverseError> for CensusError {
+        fn from(error: SparseUniverseError) -> Self {
+            Self::Rule(error)
+        }
+    }
+
+    impl From<SoupError> for CensusError {
+        fn from(error: SoupError) -> Self {
+            Self::Soup(error)
+        }
+    }
+
+    // the objects left over by the searched soups, by apgcode
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct Census {
+        soups: u64,
+        // soups still changing after the generation limit, or explosive
+        unstabilized: u64,
+        // leftover objects that do not repeat on their own
+        unclassified: u64,
+        objects: HashMap<String, u64>,
+    }
+
+    impl Census {
+        pub fn soups(&self) -> u64 {
+            self.soups
+        }
+
+        pub fn unstabilized(&self) -> u64 {
+            self.unstabilized
+        }
+
+        pub fn unclassified(&self) -> u64 {
+            self.unclassified
+        }
+
+        pub fn count(&self, apgcode: &str) -> u64 {
+            self.objects.get(apgcode).copied().unwrap_or(0)
+        }
+
+        // the most common objects first, equally common ones by apgcode
+        pub fn objects(&self) -> Vec<(&str, u64)> {
+            let mut objects: Vec<(&str, u64)> = self
+                .objects
+                .iter()
+                .map(|(apgcode, count)| (apgcode.as_str(), *count))
+                .collect();
+            objects.sort_unstable_by(|lhs, rhs| rhs.1.cmp(&lhs.1).then_with(|| lhs.0.cmp(rhs.0)));
+            objects
+        }
+    }
+
+    // runs seeded soups of a Life-like rule on an unbounded plane until they
+    // stabilize and counts the objects they leave behind, like apgsearch
+    pub struct CensusSearch {
+        rule: Rule,
+        soup: Soup,
+        height: usize,
+        width: usize,
+        max_generations: u64,
+    }
+
+    impl CensusSearch {
+        pub fn new(
+            rule: Rule,
+            soup: Soup,
+            height: usize,
+            width: usize,
+        ) -> Result<Self, CensusError> {
+            SparseUniverse::new(rule)?;
+            soup.region_on(height, width)?;
+            Ok(Self {
+                rule,
+                soup,
+                height,
+                width,
+                max_generations: 10000,
+            })
+        }
+
+        pub fn with_max_generations(mut self, max_generations: u64) -> Self {
+            self.max_generations = max_generations;
+            self
+        }
+
+        pub fn run(&self, seeds: impl IntoIterator<Item = u64>) -> Census {
+            let mut census = Census::default();
+            for seed in seeds {
+                self.run_soup(seed, &mut census);
+            }
+            census
+        }
+
+        pub fn run_soup(&self, seed: u64, census: &mut Census) {
+            census.soups += 1;
+            let game = Game::new(self.height, self.width)
+                .with_rule(self.rule)
+                .randomize_soup_seeded(&self.soup, seed)
+                .expect("the soup region is checked by new");
+            let mut universe = SparseUniverse::new(self.rule).expect("the rule is checked by new");
+            for (slot, cell) in game.slots_and_cells() {
+                if cell.is_alive() {
+                    universe.set_cell(slot.0 as i64, slot.1 as i64, true);
+                }
+            }
+
+            let mut populations = vec![universe.population()];
+            while universe.population() > 0
+                && !(universe.generation().is_multiple_of(CHECK_INTERVAL)
+                    && is_stable(&populations))
+            {
+                if universe.generation() >= self.max_generations
+                    || universe.population() > MAX_GROWTH * self.height * self.width
+                {
+                    census.unstabilized += 1;
+                    return;
+                }
+                universe.tick();
+                populations.push(universe.population());
+            }
+
+            let objects = objects::separate(self.rule, universe.live_cells())
+                .expect("the rule is checked by new");
+            for object in objects {
+                match object.apgcode() {
+                    Some(apgcode) => *census.objects.entry(apgcode.to_owned()).or_default() += 1,
+                    None => census.unclassified += 1,
+                }
+            }
+        }
+    }
+
+    fn is_stable(populations: &[usize]) -> bool {
+        (1..=MAX_STABLE_PERIOD).any(|period| {
+            let Some(start) = populations.len().checked_sub(STABLE_WINDOW + period) else {
+                return false;
+            };
+            populations[start..start + STABLE_WINDOW] == populations[start + period..]
+        })
+    }
+}
+
+pub mod objects {
+    use crate::apgcode::{self, ObjectKind};
+    use crate::game::rule::Rule;
+    use crate::period::Cycle;
+    use crate::sparse::{SparseUniverse, SparseUniverseError};
+    use std::collections::HashSet;
+
+    // an object has to repeat on its own within this many generations
+    pub const MAX_PERIOD: u64 = 240;
+    // live cells this close to each other may belong to the same object, like the
+    // two halves of a beacon
+    const CLUSTER_DISTANCE: i64 = 2;
+    const ORTHOGONAL: [(i64, i64); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Classification {
+        StillLife,
+        Oscillator {
+            period: u64,
+        },
+        // moves (rows, cols) every period
+        Spaceship {
+            period: u64,
+            translation: (i64, i64),
+        },
+        // changes before it repeats on its own, or takes longer than MAX_PERIOD
+        Unclassified,
+    }
+
+    impl Classification {
+        pub fn kind(&self) -> Option<ObjectKind> {
+            match self {
+                Classification::StillLife => Some(ObjectKind::StillLife),
+                Classification::Oscillator { .. } => Some(ObjectKind::Oscillator),
+                Classification::Spaceship { .. } => Some(ObjectKind::Spaceship),
+                Classification::Unclassified => None,
+            }
+        }
+
+        fn of(cycle: &Cycle) -> Self {
+            if cycle.is_still_life() {
+                Classification::StillLife
+            } else if cycle.is_oscillator() {
+                Classification::Oscillator {
+                    period: cycle.period,
+                }
+            } else {
+                Classification::Spaceship {
+                    period: cycle.period,
+                    translation: cycle.translation,
+                }
+            }
+        }
+    }
+
+    impl std::fmt::Display for Classification {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Classification::StillLife => write!(f, "still life"),
+                Classification::Oscillator { period } => write!(f, "oscillator p{}", period),
+                Classification::Spaceship {
+                    period,
+                    translation: (dy, dx),
+                } => write!(f, "spaceship p{} moving ({}, {})", period, dy, dx),
+                Classification::Unclassified => write!(f, "unclassified"),
+            }
+        }
+    }
+
+    // cells that repeat on their own from the start, with the live cells of each
+    // generation of one period
+    pub struct Periodic {
+        pub cycle: Cycle,
+        pub phases: Vec<Vec<(i64, i64)>>,
+    }
+
+    impl Periodic {
+        // the live cells `generation` generations after the first phase
+        fn at(&self, generation: u64) -> impl Iterator<Item = (i64, i64)> + '_ {
+            let laps = (generation / self.cycle.period) as i64;
+            let (dy, dx) = self.cycle.translation;
+            self.phases[(generation % self.cycle.period) as usize]
+                .iter()
+                .map(move |&(row, col)| (row + dy * laps, col + dx * laps))
+        }
+    }
+
+    // the cells simulated on their own on an unbounded plane, None unless they
+    // repeat from the start within MAX_PERIOD generations
+    pub fn periodic(
+        rule: Rule,
+        cells: &[(i64, i64)],
+    ) -> Result<Option<Periodic>, SparseUniverseError> {
+        let mut universe = SparseUniverse::new(rule)?;
+        for &(row, col) in cells {
+            universe.set_cell(row, col, true);
+        }
+        let mut universe = universe.with_period_detection();
+        let mut phases = vec![cells.to_vec()];
+        // a cycle is confirmed one period after the first state comes back
+        while universe.cycle().is_none() && universe.generation() < 2 * MAX_PERIOD {
+            universe.tick();
+            phases.push(universe.live_cells().collect());
+        }
+        Ok(universe
+            .cycle()
+            .filter(|cycle| cycle.pre_period == 0)
+            .map(|cycle| {
+                phases.truncate(cycle.period as usize);
+                Periodic { cycle, phases }
+            }))
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Object {
+        // sorted live cells
+        pub cells: Vec<(i64, i64)>,
+        pub classification: Classification,
+        apgcode: Option<String>,
+    }
+
+    impl Object {
+        fn new(mut cells: Vec<(i64, i64)>, periodic: Option<&Periodic>) -> Self {
+            cells.sort_unstable();
+            Self {
+                cells,
+                classification: periodic.map_or(Classification::Unclassified, |periodic| {
+                    Classification::of(&periodic.cycle)
+                }),
+                apgcode: periodic
+                    .map(|periodic| apgcode::apgcode(&periodic.cycle, &periodic.phases)),
+            }
+        }
+
+        pub fn apgcode(&self) -> Option<&str> {
+            self.apgcode.as_deref()
+        }
+    }
+
+    // splits live cells into objects, each simulated on its own to classify it:
+    // cells up to CLUSTER_DISTANCE apart are grouped, and a group that only
+    // repeats because its orthogonally connected parts repeat without touching
+    // each other, a pseudo object like two blocks side by side, is split into
+    // those parts
+    pub fn separate(
+        rule: Rule,
+        live_cells: impl IntoIterator<Item = (i64, i64)>,
+    ) -> Result<Vec<Object>, SparseUniverseError> {
+        SparseUniverse::new(rule)?;
+        let live_cells: Vec<(i64, i64)> = live_cells.into_iter().collect();
+        let mut objects: Vec<Object> = components(&live_cells, &cluster_offsets())
+            .into_iter()
+            .flat_map(|cluster| split(rule, cluster))
+            .collect();
+        objects.sort_unstable_by(|lhs, rhs| lhs.cells.cmp(&rhs.cells));
+        Ok(objects)
+    }
+
+    fn cluster_offsets() -> Vec<(i64, i64)> {
+        (-CLUSTER_DISTANCE..=CLUSTER_DISTANCE)
+            .flat_map(|dy| (-CLUSTER_DISTANCE..=CLUSTER_DISTANCE).map(move |dx| (dy, dx)))
+            .collect()
+    }
+
+    // groups of cells reachable from each other through the offsets
+    fn components(cells: &[(i64, i64)], offsets: &[(i64, i64)]) -> Vec<Vec<(i64, i64)>> {
+        let mut unvisited: HashSet<(i64, i64)> = cells.iter().copied().collect();
+        let mut components = Vec::new();
+        for &start in cells {
+            if !unvisited.remove(&start) {
+                continue;
+            }
+            let mut component = vec![start];
+            let mut next = 0;
+            while let Some(&(row, col)) = component.get(next) {
+                next += 1;
+                for &(dy, dx) in offsets {
+                    if unvisited.remove(&(row + dy, col + dx)) {
+                        component.push((row + dy, col + dx));
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    // the cluster as one object, or its parts if it is a pseudo object; parts
+    // that do not repeat on their own are merged into the closest part until
+    // every part does
+    fn split(rule: Rule, cluster: Vec<(i64, i64)>) -> Vec<Object> {
+        let periodic_of =
+            |cells: &[(i64, i64)]| periodic(rule, cells).expect("the rule is checked by separate");
+        let Some(whole) = periodic_of(&cluster) else {
+            return vec![Object::new(cluster, None)];
+        };
+
+        let mut parts = components(&cluster, &ORTHOGONAL);
+        while parts.len() > 1 {
+            let part_periodics: Vec<Option<Periodic>> =
+                parts.iter().map(|part| periodic_of(part)).collect();
+            let Some(aperiodic) = part_periodics.iter().position(Option::is_none) else {
+                if !evolve_independently(&whole, &part_periodics) {
+                    break;
+                }
+                return parts
+                    .into_iter()
+                    .zip(&part_periodics)
+                    .map(|(part, periodic)| Object::new(part, periodic.as_ref()))
+                    .collect();
+            };
+            let part = parts.remove(aperiodic);
+            let closest = (0..parts.len())
+                .min_by_key(|&index| distance(&part, &parts[index]))
+                .expect("there are at least two parts");
+            parts[closest].extend(part);
+        }
+        vec![Object::new(cluster, Some(&whole))]
+    }
+
+    // whether the parts evolving on their own always add up to the whole
+    fn evolve_independently(whole: &Periodic, parts: &[Option<Periodic>]) -> bool {
+        let parts: Vec<&Periodic> = parts.iter().flatten().collect();
+        let repeat_with_whole = parts.iter().all(|part| {
+            let laps = whole.cycle.period / part.cycle.period;
+            whole.cycle.period.is_multiple_of(part.cycle.period)
+                && (
+                    part.cycle.translation.0 * laps as i64,
+                    part.cycle.translation.1 * laps as i64,
+                ) == whole.cycle.translation
+        });
+        repeat_with_whole
+            && (0..whole.cycle.period).all(|generation| {
+                let mut together: Vec<(i64, i64)> =
+                    parts.iter().flat_map(|part| part.at(generation)).collect();
+                let mut expected = whole.phases[generation as usize].clone();
+                together.sort_unstable();
+                expected.sort_unstable();
+                together == expected
+            })
+    }
+
+    fn distance(lhs: &[(i64, i64)], rhs: &[(i64, i64)]) -> i64 {
+        lhs.iter()
+            .flat_map(|&(row, col)| {
+                rhs.iter().map(move |&(other_row, other_col)| {
+                    (row - other_row).abs().max((col - other_col).abs())
+                })
+            })
+            .min()
+            .unwrap_or(i64::MAX)
+    }
 }
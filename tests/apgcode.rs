@@ -0,0 +1,88 @@
+use game_of_life::apgcode::{self, ApgcodeError};
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::{CellBoard, Game};
+
+fn live_cells(cell_board: &CellBoard) -> Vec<(usize, usize)> {
+    cell_board
+        .slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+#[test]
+fn decodes_catagolue_codes() {
+    assert_eq!(
+        live_cells(&apgcode::decode("xs4_33").unwrap()),
+        [(0, 0), (0, 1), (1, 0), (1, 1)]
+    );
+    assert_eq!(
+        live_cells(&apgcode::decode("xp2_7").unwrap()),
+        [(0, 0), (1, 0), (2, 0)]
+    );
+    assert_eq!(
+        live_cells(&apgcode::decode("xq4_153").unwrap()),
+        [(0, 0), (0, 1), (0, 2), (1, 2), (2, 1)]
+    );
+    // a second strip and runs of blank columns
+    let pentadecathlon = apgcode::decode("xp15_4r4z4r4").unwrap();
+    assert_eq!((pentadecathlon.height(), pentadecathlon.width()), (10, 3));
+    let blocks = apgcode::decode("xs8_33yp33").unwrap();
+    assert_eq!(blocks.width(), 33);
+}
+
+#[test]
+fn encoding_round_trips() {
+    let rule = "B3/S23".parse().unwrap();
+    for code in [
+        "xs4_33",
+        "xs6_696",
+        "xs7_2596",
+        "xp2_7",
+        "xp2_7e",
+        "xp15_4r4z4r4",
+        "xq4_153",
+        "xq4_6frc",
+    ] {
+        let cell_board = apgcode::decode(code).unwrap();
+        assert_eq!(apgcode::encode(&cell_board, rule).unwrap(), code);
+    }
+
+    // any phase and orientation has the same apgcode
+    let mut cell_board = CellBoard::new(8, 8);
+    for (row, col) in [(5, 3), (5, 4), (5, 5), (4, 5), (3, 4)] {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    let mut game = Game::from_cell_board(cell_board);
+    for _ in 0..3 {
+        assert_eq!(game.apgcode().unwrap(), "xq4_153");
+        game.tick();
+    }
+}
+
+#[test]
+fn rejects_invalid_codes_and_patterns() {
+    assert!(matches!(
+        apgcode::decode("33"),
+        Err(ApgcodeError::InvalidPrefix(_))
+    ));
+    assert!(matches!(
+        apgcode::decode("xz4_33"),
+        Err(ApgcodeError::InvalidPrefix(_))
+    ));
+    assert_eq!(
+        apgcode::decode("xs4_3A").err(),
+        Some(ApgcodeError::InvalidCharacter('A'))
+    );
+    assert_eq!(
+        apgcode::decode("xs4_33y").err(),
+        Some(ApgcodeError::UnfinishedRun)
+    );
+
+    // the R-pentomino takes over a thousand generations to settle
+    let r_pentomino = apgcode::decode("xs5_271").unwrap();
+    assert_eq!(
+        apgcode::encode(&r_pentomino, Default::default()).err(),
+        Some(ApgcodeError::NotPeriodic)
+    );
+}
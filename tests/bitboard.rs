@@ -0,0 +1,100 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::topology::Topology;
+use game_of_life::game::{CellBoard, Game};
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+const GENERATIONS: usize = 24;
+
+// the same random soup once packed into words and once cell by cell
+fn soups(height: usize, width: usize, seed: usize) -> (CellBoard, CellBoard) {
+    let mut packed = CellBoard::new(height, width);
+    let mut unpacked = CellBoard::new_unpacked(height, width);
+    for row in 0..height {
+        for col in 0..width {
+            if (row * 31 + col * 17 + seed * 7 + row * col) % 5 < 2 {
+                packed.set_slot(Slot(row, col), Cell::Alive);
+                unpacked.set_slot(Slot(row, col), Cell::Alive);
+            }
+        }
+    }
+    (packed, unpacked)
+}
+
+fn cross_check(rule: &str, topology: Topology, height: usize, width: usize, seed: usize) {
+    let (packed, unpacked) = soups(height, width, seed);
+    assert!(packed.is_packed() && !unpacked.is_packed());
+    let mut packed = Game::from_cell_board(packed)
+        .with_rule(rule.parse().unwrap())
+        .with_topology(topology);
+    let mut unpacked = Game::from_cell_board(unpacked)
+        .with_rule(rule.parse().unwrap())
+        .with_topology(topology);
+    for generation in 1..=GENERATIONS {
+        packed.tick();
+        unpacked.tick();
+        assert_eq!(
+            live_cells(&packed),
+            live_cells(&unpacked),
+            "{} on a {}x{} {} in generation {}",
+            rule,
+            width,
+            height,
+            topology,
+            generation
+        );
+    }
+}
+
+#[test]
+fn packed_step_matches_cell_step_across_word_boundaries() {
+    for width in [63, 64, 65] {
+        for (seed, rule) in ["B3/S23", "B36/S23", "B2/S", "B3678/S34678"]
+            .into_iter()
+            .enumerate()
+        {
+            cross_check(rule, Topology::Torus, 20, width, seed);
+            cross_check(rule, Topology::Plane, 20, width, seed);
+        }
+    }
+}
+
+#[test]
+fn packed_step_matches_cell_step_on_every_topology() {
+    for topology in Topology::ALL {
+        for (height, width) in [(17, 63), (16, 64), (15, 65), (9, 130)] {
+            cross_check("B3/S23", topology, height, width, height + width);
+        }
+    }
+}
+
+// a glider on a torus leaves through the bottom right corner and comes back
+// through the top left, across the last word of each row
+#[test]
+fn packed_glider_wraps_around_the_torus() {
+    for width in [63, 64, 65] {
+        let mut cell_board = CellBoard::new(8, width);
+        for (row, col) in [(4, 1), (5, 2), (6, 0), (6, 1), (6, 2)] {
+            cell_board.set_slot(Slot(row, width - 4 + col), Cell::Alive);
+        }
+        let mut game = Game::from_cell_board(cell_board).with_topology(Topology::Torus);
+        for _ in 0..16 {
+            game.tick();
+        }
+        // four periods move the glider four cells down and right
+        let expected: Vec<(usize, usize)> = {
+            let mut cells: Vec<(usize, usize)> = [(4, 1), (5, 2), (6, 0), (6, 1), (6, 2)]
+                .into_iter()
+                .map(|(row, col)| ((row + 4) % 8, (width - 4 + col + 4) % width))
+                .collect();
+            cells.sort();
+            cells
+        };
+        assert_eq!(live_cells(&game), expected, "width {}", width);
+    }
+}
@@ -0,0 +1,84 @@
+use game_of_life::apgcode::{self, ObjectKind};
+use game_of_life::census::{CensusError, CensusSearch};
+use game_of_life::game::soup::{Region, Soup};
+use game_of_life::period::Cycle;
+
+fn still_life(cells: &[(i64, i64)]) -> String {
+    let cycle = Cycle {
+        pre_period: 0,
+        period: 1,
+        translation: (0, 0),
+    };
+    apgcode::apgcode(&cycle, &[cells.to_vec()])
+}
+
+#[test]
+fn still_lifes_have_their_catagolue_codes() {
+    assert_eq!(still_life(&[(0, 0), (0, 1), (1, 0), (1, 1)]), "xs4_33");
+    let beehive = [(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)];
+    assert_eq!(still_life(&beehive), "xs6_696");
+    let loaf = [(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 3), (3, 2)];
+    assert_eq!(still_life(&loaf), "xs7_2596");
+    // two blocks with 29 blank columns between them
+    let blocks = [
+        (0, 0),
+        (0, 1),
+        (1, 0),
+        (1, 1),
+        (0, 31),
+        (0, 32),
+        (1, 31),
+        (1, 32),
+    ];
+    assert_eq!(apgcode::wechsler(&blocks), "33yp33");
+    assert_eq!(ObjectKind::of("xq4_153"), Some(ObjectKind::Spaceship));
+}
+
+#[test]
+fn census_counts_common_objects() {
+    let search = CensusSearch::new("B3/S23".parse().unwrap(), Soup::new(0.5), 16, 16).unwrap();
+    // enough soups for the block to be the most common object, as in Catagolue
+    let census = search.run(0..10);
+    assert_eq!(census.soups(), 10);
+    assert_eq!(census.unstabilized(), 0);
+    let objects = census.objects();
+    assert_eq!(objects[0].0, "xs4_33");
+    assert!(census.count("xp2_7") > 0);
+    assert!(objects.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert_eq!(search.run(0..10), census);
+}
+
+// a full 3x3 square becomes a traffic light, which is counted as its four blinkers
+#[test]
+fn census_counts_the_blinkers_of_a_traffic_light() {
+    let region = Region {
+        row: 6,
+        col: 6,
+        height: 3,
+        width: 3,
+    };
+    let soup = Soup::new(1.0).with_region(region);
+    let census = CensusSearch::new("B3/S23".parse().unwrap(), soup, 16, 16)
+        .unwrap()
+        .run(0..1);
+    assert_eq!(census.objects(), [("xp2_7", 4)]);
+    assert_eq!(census.unclassified(), 0);
+}
+
+#[test]
+fn census_rejects_unsupported_rules() {
+    assert!(matches!(
+        CensusSearch::new("B2/S/C3".parse().unwrap(), Soup::new(0.5), 16, 16),
+        Err(CensusError::Rule(_))
+    ));
+    let region = "0,0,20,20".parse().unwrap();
+    assert!(matches!(
+        CensusSearch::new(
+            Default::default(),
+            Soup::new(0.5).with_region(region),
+            16,
+            16
+        ),
+        Err(CensusError::Soup(_))
+    ));
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+// writes a pattern file for one test, named after it so tests can run in parallel
+fn pattern_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("life-cli-{}-{}", std::process::id(), name));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn life(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_life"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn run(path: &Path, args: &[&str]) -> Output {
+    let mut all = vec!["run", "--from-file", path.to_str().unwrap()];
+    all.extend(args);
+    life(&all)
+}
+
+#[test]
+fn run_exits_0_when_the_board_survives() {
+    let block = pattern_file("block.rle", "x = 4, y = 4\n$b2o$b2o!\n");
+    let output = run(&block, &["--generations", "3"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "OOOO\nOXXO\nOXXO\nOOOO\nGeneration: 3\nPopulation: 4\n"
+    );
+}
+
+#[test]
+fn run_exits_1_when_the_board_dies_out() {
+    let single = pattern_file("single.rle", "x = 3, y = 3\n$bo!\n");
+    let output = run(&single, &["--generations", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .ends_with("Generation: 1\nPopulation: 0\n")
+    );
+}
+
+#[test]
+fn run_exits_2_on_a_bad_file() {
+    let missing = std::env::temp_dir().join("life-cli-missing.rle");
+    let output = run(&missing, &[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("error: ")
+    );
+
+    let malformed = pattern_file("malformed.rle", "x = 3, y = 3\nbo?o!\n");
+    let output = run(&malformed, &[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .starts_with("error: ")
+    );
+}
+
+#[test]
+fn run_writes_json() {
+    let block = pattern_file("json.rle", "x = 4, y = 4, rule = B36/S23\n$b2o$b2o!\n");
+    let output = run(&block, &["--generations", "2", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"generation\":2,\"population\":4,\"died_out\":false,\"rule\":\"B36/S23\",\"topology\":\"torus\",\"height\":4,\"width\":4,\"board\":[\"OOOO\",\"OXXO\",\"OXXO\",\"OOOO\"]}\n"
+    );
+}
+
+#[test]
+fn run_writes_the_apgcode() {
+    let glider = pattern_file("glider.rle", "x = 3, y = 3\nbo$2bo$3o!\n");
+    let output = run(&glider, &["--generations", "0", "--format", "apgcode"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "xq4_153\n");
+}
+
+#[test]
+fn run_exits_2_on_a_grid_the_topology_does_not_fit() {
+    let output = life(&[
+        "run", "--rule", "B45/S34L", "--height", "5", "--width", "6", "--seed", "1",
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: a 5x6 torus does not fit the triangular grid of B45/S34L\n"
+    );
+
+    let output = life(&[
+        "run", "--rule", "B45/S34L", "--height", "6", "--width", "6", "--seed", "1",
+    ]);
+    assert_ne!(output.status.code(), Some(2));
+}
@@ -0,0 +1,175 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{
+    CellBoard, CellBoardCreationError, FormatErrorVariant, Position, life105, life106, plaintext,
+};
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn board_live_cells(cell_board: &CellBoard) -> Vec<(usize, usize)> {
+    cell_board
+        .slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+// the variant and position of the format error a parser returned
+fn format_error<T>(
+    result: Result<T, CellBoardCreationError>,
+) -> (FormatErrorVariant, Option<Position>) {
+    match result {
+        Err(CellBoardCreationError::FormatError {
+            variant, position, ..
+        }) => (variant, position),
+        Err(error) => panic!("expected a format error, got {}", error),
+        Ok(_) => panic!("expected a format error"),
+    }
+}
+
+#[test]
+fn parses_plaintext() {
+    let pattern =
+        plaintext::parse("!Name: Glider\n!The smallest spaceship\n.O\n..O\nOOO\n").unwrap();
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.comments, ["The smallest spaceship"]);
+    assert_eq!(pattern.rule, None);
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (3, 3)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+}
+
+#[test]
+fn plaintext_round_trips() {
+    let cell_board = board_with(6, &[(0, 0), (1, 4), (3, 2), (5, 5)]);
+    let written = plaintext::write(&cell_board, Some("Scattered"));
+    assert!(written.starts_with("!Name: Scattered\nO.....\n"));
+    let pattern = plaintext::parse(&written).unwrap();
+    assert_eq!(pattern.name.as_deref(), Some("Scattered"));
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (6, 6)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        board_live_cells(&cell_board)
+    );
+}
+
+#[test]
+fn parses_life105_blocks() {
+    let pattern = life105::parse(
+        "#Life 1.05\n#D Two blocks\n#R 23/36\n#P -2 -1\n**\n**\n#P 3 1\n.*\n.*\n.*\n",
+    )
+    .unwrap();
+    assert_eq!(pattern.comments, ["Two blocks"]);
+    assert_eq!(pattern.rule, Some("B36/S23".parse().unwrap()));
+    // from (-1, -2) to (3, 4)
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (5, 7)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        [(0, 0), (0, 1), (1, 0), (1, 1), (2, 6), (3, 6), (4, 6)]
+    );
+    assert_eq!(
+        life105::parse("#Life 1.05\n#N\n*\n").unwrap().rule,
+        Some(Rule::conway())
+    );
+}
+
+#[test]
+fn life105_writes_rules_in_survival_birth_notation() {
+    for (rule, line) in [
+        ("B3/S23", "#R 23/3"),
+        ("B36/S23", "#R 23/36"),
+        ("B2/S", "#R /2"),
+        ("B2/S345/C4", "#R 345/2/4"),
+    ] {
+        let rule: Rule = rule.parse().unwrap();
+        let written = life105::write(&board_with(2, &[(0, 0)]), rule);
+        assert_eq!(written.lines().nth(1), Some(line));
+        assert_eq!(life105::parse(&written).unwrap().rule, Some(rule));
+    }
+}
+
+#[test]
+fn life105_round_trips() {
+    let cell_board = board_with(5, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2), (4, 4)]);
+    let rule: Rule = "B36/S23".parse().unwrap();
+    let pattern = life105::parse(&life105::write(&cell_board, rule)).unwrap();
+    assert_eq!(pattern.rule, Some(rule));
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        board_live_cells(&cell_board)
+    );
+}
+
+#[test]
+fn parses_life106_coordinates() {
+    let pattern = life106::parse("#Life 1.06\n#D Glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+    assert_eq!(pattern.comments, ["Glider"]);
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (3, 3)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+    let (variant, position) = format_error(life106::parse("#Life 1.06\n0 0\n1 x\n"));
+    assert!(matches!(variant, FormatErrorVariant::InvalidCoordinates(_)));
+    assert_eq!(position.map(|position| position.line), Some(3));
+}
+
+#[test]
+fn life106_round_trips() {
+    let cell_board = board_with(5, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2), (4, 4)]);
+    let pattern = life106::parse(&life106::write(&cell_board)).unwrap();
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        board_live_cells(&cell_board)
+    );
+}
+
+#[test]
+fn refuses_huge_coordinates() {
+    for input in [
+        "0 0\n9000000000000000000 0\n",
+        "0 -9000000000000000000\n0 9000000000000000000\n",
+        "0 0\n100000 100000\n",
+    ] {
+        assert!(
+            matches!(
+                format_error(life106::parse(input)).0,
+                FormatErrorVariant::PatternTooLarge { .. }
+            ),
+            "{}",
+            input
+        );
+    }
+    assert!(matches!(
+        format_error(life105::parse(
+            "#P 0 0\n*\n#P -9223372036854775808 9223372036854775807\n*\n"
+        ))
+        .0,
+        FormatErrorVariant::PatternTooLarge { .. }
+    ));
+    assert!(matches!(
+        format_error(life105::parse("#P 9223372036854775806 0\n***\n")).0,
+        FormatErrorVariant::InvalidCoordinates(_)
+    ));
+}
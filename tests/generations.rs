@@ -0,0 +1,146 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{
+    CellBoard, CellBoardCreationError, FormatErrorVariant, Game, Position, rle,
+};
+
+// the variant and position of the format error a parser returned
+fn format_error<T>(
+    result: Result<T, CellBoardCreationError>,
+) -> (FormatErrorVariant, Option<Position>) {
+    match result {
+        Err(CellBoardCreationError::FormatError {
+            variant, position, ..
+        }) => (variant, position),
+        Err(error) => panic!("expected a format error, got {}", error),
+        Ok(_) => panic!("expected a format error"),
+    }
+}
+
+// a period 3 Brian's Brain oscillator, `A` cells are alive and `B` cells dying
+const BRIANS_BRAIN_P3: &str = "x = 8, y = 8, rule = B2/S/C3\n2$3.AB$2.B2.A$2.A2.B$3.BA!\n";
+
+fn occupied_cells(game: &Game) -> Vec<(usize, usize, Cell)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| *cell != Cell::Dead)
+        .map(|(Slot(row, col), cell)| (row, col, cell))
+        .collect()
+}
+
+#[test]
+fn brians_brain_oscillates() {
+    let mut game = Game::from_rle(BRIANS_BRAIN_P3)
+        .unwrap()
+        .with_period_detection();
+    assert_eq!(game.rule(), "B2/S/C3".parse().unwrap());
+    let start = occupied_cells(&game);
+    assert_eq!(start.len(), 8);
+    // the cycle is confirmed once the repeated state comes back a second time
+    for generation in 1..=6 {
+        assert!(game.cycle().is_none());
+        game.tick();
+        assert_eq!(occupied_cells(&game) == start, generation % 3 == 0);
+    }
+    let cycle = game.cycle().unwrap();
+    assert_eq!((cycle.pre_period, cycle.period), (0, 3));
+    assert!(cycle.is_oscillator());
+}
+
+#[test]
+fn parses_multi_state_tags() {
+    let pattern = rle::parse("x = 5, y = 2, rule = B2/S/C3\n.AB$2B.A!").unwrap();
+    let cells: Vec<Cell> = pattern
+        .cell_board
+        .slots_and_cells()
+        .map(|(_, cell)| cell)
+        .collect();
+    assert_eq!(
+        cells,
+        [
+            Cell::Dead,
+            Cell::Alive,
+            Cell::Dying(1),
+            Cell::Dead,
+            Cell::Dead,
+            Cell::Dying(1),
+            Cell::Dying(1),
+            Cell::Dead,
+            Cell::Alive,
+            Cell::Dead,
+        ]
+    );
+}
+
+#[test]
+fn parses_prefixed_tags_past_state_24() {
+    let pattern = rle::parse("x = 3, y = 1, rule = B2/S/C30\nXpApD!").unwrap();
+    assert_eq!(pattern.cell_board.get_slot(Slot(0, 0)), Cell::Dying(23));
+    assert_eq!(pattern.cell_board.get_slot(Slot(0, 1)), Cell::Dying(24));
+    assert_eq!(pattern.cell_board.get_slot(Slot(0, 2)), Cell::Dying(27));
+}
+
+#[test]
+fn writes_multi_state_tags() {
+    let rule: Rule = "B2/S/C30".parse().unwrap();
+    let mut cell_board = CellBoard::new(2, 4);
+    cell_board.set_slot(Slot(0, 1), Cell::Alive);
+    cell_board.set_slot(Slot(0, 2), Cell::Dying(1));
+    cell_board.set_slot(Slot(0, 3), Cell::Dying(1));
+    cell_board.set_slot(Slot(1, 0), Cell::Dying(24));
+    cell_board.set_slot(Slot(1, 1), Cell::Dying(28));
+    let written = rle::write(&cell_board, rule);
+    assert_eq!(written, "x = 4, y = 2, rule = B2/S/C30\n.A2B$pApE!\n");
+    let parsed = rle::parse(&written).unwrap().cell_board;
+    assert!(parsed.slots_and_cells().eq(cell_board.slots_and_cells()));
+}
+
+#[test]
+fn writes_dying_cells_as_dead_for_two_state_rules() {
+    let mut cell_board = CellBoard::new(1, 3);
+    cell_board.set_slot(Slot(0, 0), Cell::Alive);
+    cell_board.set_slot(Slot(0, 1), Cell::Dying(1));
+    cell_board.set_slot(Slot(0, 2), Cell::Alive);
+    assert_eq!(
+        rle::write(&cell_board, Rule::conway()),
+        "x = 3, y = 1, rule = B3/S23\nobo!\n"
+    );
+}
+
+#[test]
+fn rejects_states_past_the_last_dying_state() {
+    let (variant, _) = format_error(rle::parse("x = 3, y = 1, rule = B2/S/C3\nABC!"));
+    assert!(matches!(variant, FormatErrorVariant::StateOutOfRange(3)));
+    let (variant, _) = format_error(rle::parse("x = 1, y = 1, rule = B2/S/C30\npF!"));
+    assert!(matches!(variant, FormatErrorVariant::StateOutOfRange(30)));
+}
+
+#[test]
+fn rejects_letters_past_x_and_dangling_prefixes() {
+    for (tag, state) in [("Y", 25), ("Z", 26), ("pY", 49)] {
+        let input = format!("x = 1, y = 1, rule = B2/S/C200\n{}!", tag);
+        let (variant, position) = format_error(rle::parse(&input));
+        assert!(matches!(variant, FormatErrorVariant::StateOutOfRange(s) if s == state));
+        assert_eq!(
+            position,
+            Some(Position {
+                line: 2,
+                column: tag.len()
+            })
+        );
+    }
+    for (body, prefix, line, column) in [
+        ("A2pb!", 'p', 2, 3),
+        ("Aq\nA!", 'q', 2, 2),
+        ("  qA$q!", 'q', 2, 6),
+        ("A$\nr 2A!", 'r', 3, 1),
+    ] {
+        let input = format!("x = 3, y = 3, rule = B2/S/C200\n{}", body);
+        let (variant, position) = format_error(rle::parse(&input));
+        assert!(
+            matches!(variant, FormatErrorVariant::UnrecognizedCharacter(c) if c == prefix),
+            "{}",
+            body
+        );
+        assert_eq!(position, Some(Position { line, column }), "{}", body);
+    }
+}
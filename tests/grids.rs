@@ -0,0 +1,158 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::{Grid, Rule};
+use game_of_life::game::topology::Topology;
+use game_of_life::game::{CellBoard, Game};
+use game_of_life::hashlife::{HashLife, HashLifeError};
+
+fn game_with(rule: &str, cells: &[(usize, usize)]) -> Game {
+    let mut cell_board = CellBoard::new(12, 12);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    Game::from_cell_board(cell_board).with_rule(rule.parse().unwrap())
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| matches!(cell, Cell::Alive))
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+#[test]
+fn grid_suffixes_round_trip() {
+    for (notation, grid) in [
+        ("B2/S34H", Grid::Hexagonal),
+        ("B2/S/C3H", Grid::Hexagonal),
+        ("B45/S6789ABL", Grid::Triangular),
+        ("B3/S23", Grid::Square),
+    ] {
+        let rule: Rule = notation.parse().unwrap();
+        assert_eq!(rule.grid(), grid);
+        assert_eq!(rule.to_string(), notation);
+    }
+    assert!("B7/S34H".parse::<Rule>().is_err());
+    assert!("B2a/S34H".parse::<Rule>().is_err());
+    assert!("B3/S23D".parse::<Rule>().is_err());
+}
+
+#[test]
+fn neighbors_are_symmetric() {
+    for grid in [Grid::Square, Grid::Hexagonal, Grid::Triangular] {
+        for row in 0..2 {
+            for col in 0..2 {
+                let offsets = grid.neighbor_offsets(row + 2, col + 2);
+                assert_eq!(offsets.len(), grid.neighbors());
+                for &(dy, dx) in offsets {
+                    let (neighbor_row, neighbor_col) =
+                        ((row + 2) as i32 + dy, (col + 2) as i32 + dx);
+                    assert!(
+                        grid.neighbor_offsets(neighbor_row as usize, neighbor_col as usize)
+                            .contains(&(-dy, -dx))
+                    );
+                }
+            }
+        }
+    }
+}
+
+// a dead cell with exactly its NE and SW square neighbors alive is born on the
+// square grid, but neither is a neighbor on the skewed hexagonal grid
+#[test]
+fn hexagonal_rules_count_six_neighbors() {
+    let diagonal = [(3, 5), (5, 3)];
+    let mut game = game_with("B2/S", &diagonal);
+    game.tick();
+    assert!(live_cells(&game).contains(&(4, 4)));
+    let mut game = game_with("B2/SH", &diagonal);
+    game.tick();
+    assert!(!live_cells(&game).contains(&(4, 4)));
+
+    // the six neighbors of a cell
+    let neighbors = [(4, 4), (4, 5), (5, 4), (5, 6), (6, 5), (6, 6)];
+    let mut game = game_with("B6/S012345H", &neighbors);
+    game.tick();
+    assert!(live_cells(&game).contains(&(5, 5)));
+}
+
+// every neighbor across a join sees the cell back as often as the cell sees it
+fn joins_keep_neighbors(grid: Grid, topology: Topology, height: usize, width: usize) -> bool {
+    let neighbors = |slot: Slot| {
+        grid.neighbor_offsets(slot.0, slot.1)
+            .iter()
+            .filter_map(move |offset| topology.neighbor(slot, *offset, height, width))
+    };
+    (0..height).all(|row| {
+        (0..width).all(|col| {
+            let slot = Slot(row, col);
+            neighbors(slot).all(|neighbor| {
+                neighbors(neighbor).filter(|back| *back == slot).count()
+                    == neighbors(slot).filter(|other| *other == neighbor).count()
+            })
+        })
+    })
+}
+
+#[test]
+fn grids_fit_the_topologies_that_keep_their_neighbors() {
+    for grid in [Grid::Square, Grid::Hexagonal, Grid::Triangular] {
+        for topology in Topology::ALL {
+            for height in 3..=6 {
+                for width in 3..=6 {
+                    assert_eq!(
+                        grid.fits(topology, height, width),
+                        joins_keep_neighbors(grid, topology, height, width),
+                        "{} {} {}x{}",
+                        grid,
+                        topology,
+                        height,
+                        width
+                    );
+                }
+            }
+        }
+    }
+    assert!(Grid::Hexagonal.fits(Topology::Torus, 5, 7));
+    assert!(!Grid::Triangular.fits(Topology::Torus, 5, 6));
+    assert!(!Grid::Triangular.fits(Topology::HorizontalCylinder, 6, 5));
+    assert!(Grid::Triangular.fits(Topology::Plane, 5, 5));
+}
+
+// a lone cell on the bottom row of a hexagonal torus with an odd height gives
+// birth to its six neighbors across the edges
+#[test]
+fn hexagonal_neighbors_wrap_on_odd_tori() {
+    let mut cell_board = CellBoard::new(5, 7);
+    cell_board.set_slot(Slot(4, 0), Cell::Alive);
+    let mut game = Game::from_cell_board(cell_board).with_rule("B1/SH".parse().unwrap());
+    game.tick();
+    assert_eq!(
+        live_cells(&game),
+        [(0, 0), (0, 1), (3, 0), (3, 6), (4, 1), (4, 6)]
+    );
+}
+
+// with B1 a single triangle gives birth to each of its 12 neighbors
+#[test]
+fn triangular_rules_count_twelve_neighbors() {
+    let mut game = game_with("B1/SL", &[(6, 6)]);
+    game.tick();
+    let mut expected: Vec<(usize, usize)> = Grid::Triangular
+        .neighbor_offsets(6, 6)
+        .iter()
+        .map(|(dy, dx)| ((6 + dy) as usize, (6 + dx) as usize))
+        .collect();
+    expected.sort();
+    assert_eq!(live_cells(&game), expected);
+    assert_eq!(game.grid(), Grid::Triangular);
+}
+
+#[test]
+fn hashlife_rejects_other_grids() {
+    let rule: Rule = "B2/S34H".parse().unwrap();
+    assert!(!rule.is_life_like());
+    assert!(matches!(
+        HashLife::from_cell_board(&CellBoard::new(8, 8), rule),
+        Err(HashLifeError::UnsupportedRule)
+    ));
+}
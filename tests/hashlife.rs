@@ -0,0 +1,158 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{CellBoard, Game};
+use game_of_life::hashlife::{HashLife, HashLifeError};
+use game_of_life::sparse::SparseUniverse;
+
+const BOARD_SIZE: usize = 64;
+const SOUP_SIZE: usize = 16;
+
+// a soup in the middle of a board large enough that nothing wraps around the
+// torus within the simulated generations
+fn centered_soup(seed: usize) -> CellBoard {
+    let mut cell_board = CellBoard::new(BOARD_SIZE, BOARD_SIZE);
+    let offset = (BOARD_SIZE - SOUP_SIZE) / 2;
+    for row in 0..SOUP_SIZE {
+        for col in 0..SOUP_SIZE {
+            if (row * 31 + col * 17 + seed * 7) % 5 < 2 {
+                cell_board.set_slot(Slot(offset + row, offset + col), Cell::Alive);
+            }
+        }
+    }
+    cell_board
+}
+
+fn live_cells(game: &Game) -> Vec<(i64, i64)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| matches!(cell, Cell::Alive))
+        .map(|(Slot(row, col), _)| (row as i64, col as i64))
+        .collect()
+}
+
+fn cross_check(rule: Rule, seed: usize, generations: u64) {
+    let cell_board = centered_soup(seed);
+    let mut hash_life = HashLife::from_cell_board(&cell_board, rule).unwrap();
+    let mut game = Game::from_cell_board(cell_board).with_rule(rule);
+    for _ in 0..generations {
+        game.tick();
+    }
+    hash_life.step(generations).unwrap();
+    assert_eq!(hash_life.generation(), generations);
+    assert_eq!(hash_life.live_cells(), live_cells(&game));
+}
+
+#[test]
+fn step_matches_tick_for_conway() {
+    for (seed, generations) in [(0, 1), (1, 2), (2, 7), (3, 13), (4, 16), (5, 23)] {
+        cross_check(Rule::conway(), seed, generations);
+    }
+}
+
+#[test]
+fn step_matches_tick_for_other_rules() {
+    for rule in ["B36/S23", "B2/S", "B3678/S34678"] {
+        cross_check(rule.parse().unwrap(), 1, 11);
+    }
+}
+
+#[test]
+fn advance_pow2_moves_glider() {
+    let cell_board = CellBoard::from_rle("x = 3, y = 3\nbo$2bo$3o!")
+        .ok()
+        .unwrap();
+    let mut hash_life = HashLife::from_cell_board(&cell_board, Rule::conway()).unwrap();
+    let before = hash_life.live_cells();
+    hash_life.advance_pow2(20).unwrap();
+    let shift = (1 << 20) / 4;
+    let expected: Vec<(i64, i64)> = before
+        .iter()
+        .map(|(row, col)| (row + shift, col + shift))
+        .collect();
+    assert_eq!(hash_life.generation(), 1 << 20);
+    assert_eq!(hash_life.live_cells(), expected);
+}
+
+#[test]
+fn advance_pow2_moves_glider_to_the_largest_universe() {
+    let cell_board = CellBoard::from_rle("x = 3, y = 3\nbo$2bo$3o!")
+        .ok()
+        .unwrap();
+    let mut hash_life = HashLife::from_cell_board(&cell_board, Rule::conway()).unwrap();
+    let before = hash_life.live_cells();
+    hash_life.advance_pow2(59).unwrap();
+    let shift = 1 << 57;
+    let expected: Vec<(i64, i64)> = before
+        .iter()
+        .map(|(row, col)| (row + shift, col + shift))
+        .collect();
+    assert_eq!(hash_life.generation(), 1 << 59);
+    assert_eq!(hash_life.live_cells(), expected);
+}
+
+#[test]
+fn refuses_to_advance_past_the_largest_universe() {
+    let block = CellBoard::from_rle("x = 2, y = 2\n2o$2o!").ok().unwrap();
+    let mut hash_life = HashLife::from_cell_board(&block, Rule::conway()).unwrap();
+    for k in [60, 63, 64, u32::MAX] {
+        assert_eq!(
+            hash_life.advance_pow2(k),
+            Err(HashLifeError::UniverseTooLarge)
+        );
+    }
+    assert_eq!(hash_life.generation(), 0);
+    assert_eq!(
+        hash_life.step(u64::MAX),
+        Err(HashLifeError::UniverseTooLarge)
+    );
+    assert_eq!(hash_life.generation(), (1 << 60) - 1);
+    assert_eq!(hash_life.live_cells(), [(0, 0), (0, 1), (1, 0), (1, 1)]);
+    assert_eq!(
+        hash_life.step(u64::MAX - (1 << 60) + 2),
+        Err(HashLifeError::GenerationOverflow)
+    );
+    assert_eq!(hash_life.generation(), (1 << 60) - 1);
+}
+
+#[test]
+fn refuses_cells_past_the_largest_universe() {
+    let mut hash_life = HashLife::new(Rule::conway()).unwrap();
+    hash_life.set_cell(-5, 3, true).unwrap();
+    assert_eq!(
+        hash_life.set_cell(i64::MAX, 0, true),
+        Err(HashLifeError::UniverseTooLarge)
+    );
+    assert_eq!(
+        hash_life.set_cell(0, i64::MIN, true),
+        Err(HashLifeError::UniverseTooLarge)
+    );
+    hash_life.set_cell(1 << 60, -(1 << 60), true).unwrap();
+    assert_eq!(hash_life.live_cells(), [(-5, 3), (1 << 60, -(1 << 60))]);
+
+    let mut universe = SparseUniverse::new(Rule::conway()).unwrap();
+    universe.set_cell(0, 0, true);
+    assert!(universe.to_macrocell().is_ok());
+    universe.set_cell(i64::MAX, i64::MAX, true);
+    assert_eq!(
+        universe.to_macrocell(),
+        Err(HashLifeError::UniverseTooLarge)
+    );
+}
+
+#[test]
+fn to_cell_board_round_trips() {
+    let cell_board = centered_soup(2);
+    let hash_life = HashLife::from_cell_board(&cell_board, Rule::conway()).unwrap();
+    assert_eq!(
+        hash_life.to_cell_board(BOARD_SIZE, BOARD_SIZE).to_string(),
+        cell_board.to_string()
+    );
+}
+
+#[test]
+fn rejects_birth_on_zero_neighbors() {
+    let rule: Rule = "B0/S8".parse().unwrap();
+    assert_eq!(
+        HashLife::new(rule).err(),
+        Some(HashLifeError::BirthOnZeroNeighbors)
+    );
+}
@@ -0,0 +1,111 @@
+use game_of_life::game::cell::{Action, Cell, Slot};
+use game_of_life::game::{CellBoard, Game};
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn game_with(rule: &str, size: usize, cells: &[(usize, usize)]) -> Game {
+    Game::from_cell_board(board_with(size, cells)).with_rule(rule.parse().unwrap())
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+const GLIDER: [(usize, usize); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+#[test]
+fn stepping_back_and_forward_restores_identical_boards() {
+    let mut game = game_with("B3/S23", 12, &GLIDER).with_history(100);
+    let mut boards = vec![live_cells(&game)];
+    for _ in 0..10 {
+        game.tick();
+        boards.push(live_cells(&game));
+    }
+    for generation in (0..10).rev() {
+        assert!(game.step_back());
+        assert_eq!(game.generation(), generation);
+        assert_eq!(live_cells(&game), boards[generation]);
+    }
+    assert!(!game.step_back());
+    for (generation, board) in boards.iter().enumerate().skip(1) {
+        game.tick();
+        assert_eq!(game.generation(), generation);
+        assert_eq!(&live_cells(&game), board);
+    }
+    // past the boards that were stepped back over the game goes on ticking
+    game.tick();
+    assert_eq!(game.generation(), 11);
+    assert_eq!(live_cells(&game).len(), 5);
+}
+
+#[test]
+fn cell_edits_are_stepped_back_over() {
+    let mut game = game_with("B3/S23", 12, &GLIDER).with_history(100);
+    game.tick();
+    let ticked = live_cells(&game);
+    game.apply_action(Slot(8, 8), Action::Live);
+    game.apply_action(Slot(8, 9), Action::Live);
+    let edited = live_cells(&game);
+    assert_eq!(edited.len(), 7);
+
+    assert!(game.step_back());
+    assert_eq!(game.generation(), 1);
+    assert_eq!(live_cells(&game).len(), 6);
+    assert!(game.step_back());
+    assert_eq!(game.generation(), 1);
+    assert_eq!(live_cells(&game), ticked);
+
+    // stepping forward replays the edits before the next generation
+    game.tick();
+    game.tick();
+    assert_eq!(game.generation(), 1);
+    assert_eq!(live_cells(&game), edited);
+
+    // a new edit drops the boards that were stepped back over
+    assert!(game.step_back());
+    game.clear();
+    assert_eq!(live_cells(&game), []);
+    game.tick();
+    assert_eq!(game.generation(), 2);
+    assert_eq!(live_cells(&game), []);
+    assert!(game.step_back());
+    assert!(game.step_back());
+    assert_eq!(live_cells(&game).len(), 6);
+}
+
+#[test]
+fn history_keeps_at_most_its_capacity() {
+    let mut game = game_with("B3/S23", 12, &GLIDER).with_history(3);
+    for _ in 0..5 {
+        game.tick();
+    }
+    game.apply_action(Slot(0, 0), Action::Live);
+    assert_eq!(game.history_len(), 3);
+    assert!(game.step_back());
+    assert!(game.step_back());
+    assert!(game.step_back());
+    assert!(!game.step_back());
+    assert_eq!(game.generation(), 3);
+}
+
+#[test]
+fn soups_are_stepped_back_over() {
+    let mut game = game_with("B3/S23", 12, &GLIDER).with_history(10);
+    game.tick();
+    let ticked = live_cells(&game);
+    let mut game = game.randomize_seeded(0.5, 7);
+    assert_ne!(live_cells(&game), ticked);
+    assert!(game.step_back());
+    assert_eq!(game.generation(), 1);
+    assert_eq!(live_cells(&game), ticked);
+}
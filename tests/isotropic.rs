@@ -0,0 +1,146 @@
+use game_of_life::game::cell::{Action, Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{CellBoard, Game};
+use game_of_life::hashlife::{HashLife, HashLifeError};
+
+const LETTERS: [&str; 9] = [
+    "",
+    "ce",
+    "cekain",
+    "cekainyqjr",
+    "cekainyqjrtwz",
+    "cekainyqjr",
+    "cekain",
+    "ce",
+    "",
+];
+
+fn game_with(rule: &str, cells: &[(usize, usize)]) -> Game {
+    let mut cell_board = CellBoard::new(16, 16);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    Game::from_cell_board(cell_board).with_rule(rule.parse().unwrap())
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| matches!(cell, Cell::Alive))
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+#[test]
+fn letters_partition_each_neighbor_count() {
+    for (count, letters) in LETTERS.iter().enumerate() {
+        let mut named = [0; 256];
+        for letter in letters.chars() {
+            let rule: Rule = format!("B{}{}/S", count, letter).parse().unwrap();
+            assert!(rule.is_isotropic());
+            for configuration in 0..=u8::MAX {
+                if let Action::Live = rule.configuration_action(Cell::Dead, configuration) {
+                    assert_eq!(configuration.count_ones() as usize, count);
+                    named[configuration as usize] += 1;
+                }
+            }
+        }
+        if !letters.is_empty() {
+            for configuration in (0..=u8::MAX).filter(|c| c.count_ones() as usize == count) {
+                assert_eq!(
+                    named[configuration as usize], 1,
+                    "{}: {:08b}",
+                    count, configuration
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn all_letters_are_the_totalistic_rule() {
+    let rule: Rule = "B3cekainyqjr/S2cekain3cekainyqjr".parse().unwrap();
+    assert_eq!(rule, Rule::conway());
+    assert!(rule.is_life_like());
+    assert_eq!(rule.to_string(), "B3/S23");
+}
+
+#[test]
+fn hensel_notation_round_trips() {
+    for notation in [
+        "B2-a/S12",
+        "B3/S2-i34q",
+        "B2ci3ai4c8/S02ae3eijkq4iz5ar6i7e",
+        "B2n/S/C4",
+    ] {
+        let rule: Rule = notation.parse().unwrap();
+        assert_eq!(rule.to_string(), notation);
+        assert_eq!(rule.to_string().parse::<Rule>().unwrap(), rule);
+    }
+    assert_eq!(
+        "b2-a/s12".parse::<Rule>().unwrap(),
+        "B2cekin/S12".parse::<Rule>().unwrap()
+    );
+    assert!("B2x/S23".parse::<Rule>().is_err());
+    assert!("B1a/S23".parse::<Rule>().is_err());
+    assert!("B2-/S23".parse::<Rule>().is_err());
+}
+
+// in Just Friends a domino is a still life: the cells beside it only have two
+// adjacent live neighbors, which do not give birth
+#[test]
+fn just_friends_domino_is_still() {
+    let domino = [(7, 7), (7, 8)];
+    let mut game = game_with("B2-a/S12", &domino);
+    for _ in 0..4 {
+        game.tick();
+        assert_eq!(live_cells(&game), domino);
+    }
+    let mut game = game_with("B2/S12", &domino);
+    game.tick();
+    assert_ne!(live_cells(&game), domino);
+}
+
+// in Just Friends two cells a knight's move apart give birth between them
+#[test]
+fn just_friends_knight_pair_gives_birth() {
+    let mut game = game_with("B2-a/S12", &[(7, 7), (8, 9)]);
+    game.tick();
+    assert!(live_cells(&game).contains(&(7, 8)));
+    assert!(live_cells(&game).contains(&(8, 8)));
+}
+
+// in tlife the middle of a blinker, with its live neighbors opposite each other,
+// does not survive, so the blinker dies out instead of oscillating
+#[test]
+fn tlife_blinker_dies_out() {
+    let blinker = [(6, 7), (7, 7), (8, 7)];
+    let mut game = game_with("B3/S2-i34q", &blinker);
+    game.tick();
+    assert_eq!(live_cells(&game), [(7, 6), (7, 8)]);
+    game.tick();
+    assert!(live_cells(&game).is_empty());
+
+    let mut game = game_with("B3/S23", &blinker);
+    game.tick();
+    game.tick();
+    assert_eq!(live_cells(&game), blinker);
+}
+
+// the block survives in tlife, each of its cells having three live neighbors
+#[test]
+fn tlife_block_is_still() {
+    let block = [(7, 7), (7, 8), (8, 7), (8, 8)];
+    let mut game = game_with("B3/S2-i34q", &block);
+    game.tick();
+    assert_eq!(live_cells(&game), block);
+}
+
+#[test]
+fn hashlife_rejects_isotropic_rules() {
+    let rule: Rule = "B2-a/S12".parse().unwrap();
+    assert!(!rule.is_life_like());
+    assert!(matches!(
+        HashLife::from_cell_board(&CellBoard::new(8, 8), rule),
+        Err(HashLifeError::UnsupportedRule)
+    ));
+}
@@ -0,0 +1,269 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::{Neighborhood, Rule, RuleParseError};
+use game_of_life::game::topology::Topology;
+use game_of_life::game::{CellBoard, Game};
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+// Bosco's Rule
+const BOSCO: &str = "R5,C0,M1,S34..58,B34..45,NM";
+
+// a spaceship of Bosco's Rule, five rows up every six generations
+const SHIP: [&str; 11] = [
+    "....##....",
+    "..######..",
+    "..######..",
+    ".########.",
+    ".########.",
+    "####..####",
+    "###....###",
+    "###....###",
+    ".##....##.",
+    "..##..##..",
+    "...####...",
+];
+
+// a board with live cells scattered over it, wrapped by `topology`
+fn scattered_board(topology: Topology) -> CellBoard {
+    let mut cell_board = CellBoard::new(7, 9);
+    cell_board.set_topology(topology);
+    for row in 0..7 {
+        for col in 0..9 {
+            if (row * 7 + col * 13 + row * col) % 5 < 2 {
+                cell_board.set_slot(Slot(row, col), Cell::Alive);
+            }
+        }
+    }
+    cell_board
+}
+
+fn in_neighborhood(neighborhood: Neighborhood, range: i32, dy: i32, dx: i32) -> bool {
+    match neighborhood {
+        Neighborhood::Moore => dy.abs() <= range && dx.abs() <= range,
+        Neighborhood::VonNeumann => dy.abs() + dx.abs() <= range,
+        // closer than range + 1/2, doubled to stay with integers
+        Neighborhood::Circular => 4 * (dy * dy + dx * dx) < (2 * range + 1).pow(2),
+    }
+}
+
+// the live cells in the neighborhood of every slot, one offset at a time
+fn counted_one_by_one(
+    cell_board: &CellBoard,
+    neighborhood: Neighborhood,
+    range: i32,
+    middle: bool,
+) -> Vec<u32> {
+    cell_board
+        .slots_and_cells()
+        .map(|(slot, _)| {
+            let mut count = 0;
+            for dy in -range..=range {
+                for dx in -range..=range {
+                    if !in_neighborhood(neighborhood, range, dy, dx)
+                        || (!middle && (dy, dx) == (0, 0))
+                    {
+                        continue;
+                    }
+                    if let Some(neighbor) = cell_board.neighbor(slot, (dy, dx))
+                        && cell_board.get_slot(neighbor).is_alive()
+                    {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        })
+        .collect()
+}
+
+fn ship_game(height: usize, width: usize, top: usize, left: usize) -> Game {
+    let mut cell_board = CellBoard::new(height, width);
+    for (row, line) in SHIP.iter().enumerate() {
+        for (col, c) in line.chars().enumerate() {
+            if c == '#' {
+                cell_board.set_slot(Slot(top + row, left + col), Cell::Alive);
+            }
+        }
+    }
+    Game::from_cell_board(cell_board)
+        .with_rule(BOSCO.parse().unwrap())
+        .with_topology(Topology::Plane)
+}
+
+#[test]
+fn prefix_sums_count_like_single_offsets() {
+    let neighborhoods = [
+        Neighborhood::Moore,
+        Neighborhood::VonNeumann,
+        Neighborhood::Circular,
+    ];
+    for topology in Topology::ALL {
+        let cell_board = scattered_board(topology);
+        for neighborhood in neighborhoods {
+            for range in 1..=4 {
+                for middle in [false, true] {
+                    assert_eq!(
+                        cell_board.neighborhood_counts(neighborhood, range, middle),
+                        counted_one_by_one(&cell_board, neighborhood, range as i32, middle),
+                        "{:?} {:?} range {} middle {}",
+                        topology,
+                        neighborhood,
+                        range,
+                        middle
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn moore_and_von_neumann_neighborhoods_differ_in_the_corners() {
+    let mut cell_board = board_with(9, &[(4, 4)]);
+    cell_board.set_topology(Topology::Plane);
+    let moore = cell_board.neighborhood_counts(Neighborhood::Moore, 2, false);
+    let von_neumann = cell_board.neighborhood_counts(Neighborhood::VonNeumann, 2, false);
+    let count_at = |counts: &[u32], row: usize, col: usize| counts[row * 9 + col];
+    // two rows and two columns away
+    assert_eq!(count_at(&moore, 2, 2), 1);
+    assert_eq!(count_at(&von_neumann, 2, 2), 0);
+    // two rows away in the same column
+    assert_eq!(count_at(&moore, 2, 4), 1);
+    assert_eq!(count_at(&von_neumann, 2, 4), 1);
+    // one row and one column away
+    assert_eq!(count_at(&von_neumann, 3, 3), 1);
+    // three rows away
+    assert_eq!(count_at(&moore, 1, 4), 0);
+    assert_eq!(moore.iter().sum::<u32>(), 24);
+    assert_eq!(von_neumann.iter().sum::<u32>(), 12);
+}
+
+#[test]
+fn middle_cell_counts_only_when_included() {
+    let mut cell_board = board_with(5, &[(2, 2)]);
+    cell_board.set_topology(Topology::Plane);
+    for neighborhood in [Neighborhood::Moore, Neighborhood::VonNeumann] {
+        let without = cell_board.neighborhood_counts(neighborhood, 2, false);
+        let with = cell_board.neighborhood_counts(neighborhood, 2, true);
+        assert_eq!(without[2 * 5 + 2], 0);
+        assert_eq!(with[2 * 5 + 2], 1);
+        assert_eq!(without[2 * 5 + 3], 1);
+        assert_eq!(with[2 * 5 + 3], 1);
+    }
+
+    // a lone cell survives S1 only if it counts itself
+    let lone = |middle: &str| {
+        let rule = format!("R2,C0,{},S1..1,B9..9,NN", middle);
+        let mut game = Game::from_cell_board(board_with(5, &[(2, 2)]))
+            .with_rule(rule.parse().unwrap())
+            .with_topology(Topology::Plane);
+        game.tick();
+        live_cells(&game)
+    };
+    assert_eq!(lone("M1"), [(2, 2)]);
+    assert_eq!(lone("M0"), []);
+}
+
+#[test]
+fn ship_travels_five_rows_every_six_generations() {
+    let mut game = ship_game(40, 14, 28, 2).with_period_detection();
+    let start = live_cells(&game);
+    assert_eq!(start.len(), 62);
+    for trip in 1..=4 {
+        for _ in 0..6 {
+            game.tick();
+        }
+        let moved: Vec<(usize, usize)> = start
+            .iter()
+            .map(|&(row, col)| (row - 5 * trip, col))
+            .collect();
+        assert_eq!(live_cells(&game), moved);
+    }
+    let cycle = game.cycle().unwrap();
+    assert_eq!((cycle.pre_period, cycle.period), (0, 6));
+    assert_eq!(cycle.translation, (-5, 0));
+}
+
+#[test]
+fn range_one_rules_are_their_life_like_counterparts() {
+    let conway = Rule::conway();
+    for notation in [
+        "R1,C0,M0,S2..3,B3..3,NM",
+        "R1,C0,M1,S3..4,B3..3,NM",
+        "R1,C0,M0,S2..3,B3..3,NC",
+    ] {
+        let ltl: Rule = notation.parse().unwrap();
+        assert!(ltl.is_life_like() && !ltl.is_larger_than_life());
+        for count in 0..=8 {
+            assert_eq!(ltl.born(count), conway.born(count), "{}", notation);
+            assert_eq!(ltl.survives(count), conway.survives(count), "{}", notation);
+        }
+        // written back the way it was given
+        assert_eq!(ltl.to_string(), notation);
+        assert_eq!(ltl.to_string().parse::<Rule>(), Ok(ltl));
+    }
+
+    // the Larger than Life counts of range 1 step a board the way Life does
+    let cell_board = scattered_board(Topology::Torus);
+    let counts = cell_board.neighborhood_counts(Neighborhood::Moore, 1, false);
+    let stepped: Vec<(usize, usize)> = cell_board
+        .slots_and_cells()
+        .zip(counts)
+        .filter(|((_, cell), count)| {
+            let count = *count as usize;
+            if cell.is_alive() {
+                conway.survives(count)
+            } else {
+                conway.born(count)
+            }
+        })
+        .map(|((Slot(row, col), _), _)| (row, col))
+        .collect();
+    let mut game = Game::from_cell_board(scattered_board(Topology::Torus));
+    game.tick();
+    assert_eq!(live_cells(&game), stepped);
+}
+
+#[test]
+fn rejects_counts_outside_the_neighborhood() {
+    for (notation, error) in [
+        (
+            "R1,C0,M0,S0..4000000000,B3..3,NM",
+            RuleParseError::NeighborhoodCountOutOfRange(4000000000, 8),
+        ),
+        (
+            "R1,C0,M0,S2..3,B3..9,NM",
+            RuleParseError::NeighborhoodCountOutOfRange(9, 8),
+        ),
+        (
+            "R2,C0,M1,S1..14,B3..3,NN",
+            RuleParseError::NeighborhoodCountOutOfRange(14, 13),
+        ),
+        (
+            "R1,C0,M0,S3..2,B3..3,NM",
+            RuleParseError::EmptyCountRange(3, 2),
+        ),
+        (
+            "R5,C0,M1,S34..58,B45..34,NM",
+            RuleParseError::EmptyCountRange(45, 34),
+        ),
+    ] {
+        assert_eq!(notation.parse::<Rule>(), Err(error), "{}", notation);
+    }
+    assert!("R1,C0,M1,S0..9,B0..8,NM".parse::<Rule>().is_ok());
+    assert!("R2,C0,M1,S1..13,B3..3,NN".parse::<Rule>().is_ok());
+}
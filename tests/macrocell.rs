@@ -0,0 +1,65 @@
+use game_of_life::game::rule::Rule;
+use game_of_life::hashlife::HashLife;
+
+// an R-pentomino in the middle of a 16x16 node, written the way Golly does:
+// 8x8 leaves with one `$` terminated row each and trailing dead cells and rows
+// left out, then `level nw ne sw se` lines numbering the nodes from 1 with 0
+// for an empty child
+const R_PENTOMINO: &str = "[M2] (golly 4.2)
+#R B3/S23
+#G 12
+$$$$$$.......*$......**$
+$$$$$$*$
+.......*$
+4 1 2 3 0
+";
+
+#[test]
+fn parses_a_golly_macrocell_file() {
+    let hash_life = HashLife::from_macrocell(R_PENTOMINO).unwrap();
+    assert_eq!(hash_life.rule(), Rule::conway());
+    assert_eq!(hash_life.generation(), 12);
+    assert_eq!(hash_life.population(), 5);
+    // Golly puts (0, 0) in the middle of the root node
+    assert_eq!(
+        hash_life.live_cells(),
+        [(-2, -1), (-2, 0), (-1, -2), (-1, -1), (0, -1)]
+    );
+}
+
+#[test]
+fn round_trips_through_hash_life() {
+    let mut hash_life = HashLife::from_macrocell(R_PENTOMINO).unwrap();
+    // the R-pentomino grows into a pattern spread across many shared nodes
+    hash_life.step(1103).unwrap();
+    let written = hash_life.to_macrocell();
+    let mut read = HashLife::from_macrocell(&written).unwrap();
+    assert_eq!(read.rule(), hash_life.rule());
+    assert_eq!(read.generation(), 1115);
+    assert_eq!(read.population(), hash_life.population());
+    let normalized = |hash_life: &HashLife| {
+        let bounding_box = hash_life.bounding_box().unwrap();
+        hash_life
+            .live_cells()
+            .into_iter()
+            .map(|(row, col)| (row - bounding_box.top, col - bounding_box.left))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(normalized(&read), normalized(&hash_life));
+    // and both go on the same way
+    hash_life.step(100).unwrap();
+    read.step(100).unwrap();
+    assert_eq!(normalized(&read), normalized(&hash_life));
+    assert_eq!(read.to_macrocell().lines().nth(2), Some("#G 1215"));
+}
+
+#[test]
+fn keeps_other_rules() {
+    let rule: Rule = "B36/S23".parse().unwrap();
+    let macrocell = R_PENTOMINO.replace("B3/S23", "B36/S23");
+    let hash_life = HashLife::from_macrocell(&macrocell).unwrap();
+    assert_eq!(hash_life.rule(), rule);
+    let read = HashLife::from_macrocell(&hash_life.to_macrocell()).unwrap();
+    assert_eq!(read.rule(), rule);
+    assert_eq!(read.live_cells(), hash_life.live_cells());
+}
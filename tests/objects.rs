@@ -0,0 +1,89 @@
+use game_of_life::apgcode::{self, ObjectKind};
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::{CellBoard, Game};
+use game_of_life::objects::{self, Classification};
+
+fn game_with(cells: &[(usize, usize)]) -> Game {
+    let mut cell_board = CellBoard::new(20, 20);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    Game::from_cell_board(cell_board)
+}
+
+fn shifted(apgcode: &str, top: usize, left: usize) -> Vec<(usize, usize)> {
+    apgcode::decode(apgcode)
+        .unwrap()
+        .slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (top + row, left + col))
+        .collect()
+}
+
+#[test]
+fn classifies_objects_on_their_own() {
+    let mut cells = shifted("xs4_33", 1, 1);
+    cells.extend(shifted("xp2_7", 1, 10));
+    cells.extend(shifted("xq4_153", 10, 10));
+    let objects = game_with(&cells).objects().unwrap();
+    let classifications: Vec<Classification> =
+        objects.iter().map(|object| object.classification).collect();
+    assert_eq!(
+        classifications,
+        [
+            Classification::StillLife,
+            Classification::Oscillator { period: 2 },
+            Classification::Spaceship {
+                period: 4,
+                translation: (-1, 1)
+            },
+        ]
+    );
+    assert_eq!(objects[2].apgcode(), Some("xq4_153"));
+    assert_eq!(
+        objects[2].classification.kind(),
+        Some(ObjectKind::Spaceship)
+    );
+}
+
+// two blocks one cell apart are two objects, but the halves of a beacon are one
+#[test]
+fn splits_pseudo_objects_only() {
+    let mut bi_block = shifted("xs4_33", 1, 1);
+    bi_block.extend(shifted("xs4_33", 1, 4));
+    let objects = game_with(&bi_block).objects().unwrap();
+    assert_eq!(objects.len(), 2);
+    assert!(
+        objects
+            .iter()
+            .all(|object| object.apgcode() == Some("xs4_33"))
+    );
+
+    // a blinker diagonally two cells from a block
+    let mut pseudo_oscillator = shifted("xs4_33", 1, 1);
+    pseudo_oscillator.extend(shifted("xp2_7", 4, 4));
+    let objects = game_with(&pseudo_oscillator).objects().unwrap();
+    let apgcodes: Vec<Option<&str>> = objects.iter().map(|object| object.apgcode()).collect();
+    assert_eq!(apgcodes, [Some("xs4_33"), Some("xp2_7")]);
+
+    let beacon = [(1, 1), (1, 2), (2, 1), (3, 4), (4, 3), (4, 4)];
+    let objects = game_with(&beacon).objects().unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].apgcode(), Some("xp2_318c"));
+
+    // the boat's orthogonally connected parts die on their own
+    let objects = game_with(&shifted("xs5_253", 1, 1)).objects().unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].classification, Classification::StillLife);
+}
+
+#[test]
+fn unsettled_objects_are_unclassified() {
+    let r_pentomino = shifted("xs5_271", 8, 8);
+    let objects = game_with(&r_pentomino).objects().unwrap();
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].classification, Classification::Unclassified);
+    assert_eq!(objects[0].apgcode(), None);
+
+    assert!(objects::separate("B2/S/C3".parse().unwrap(), [(0, 0)]).is_err());
+}
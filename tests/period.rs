@@ -0,0 +1,151 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::{CellBoard, Game};
+use game_of_life::period::{Cycle, PeriodDetector};
+use game_of_life::sparse::SparseUniverse;
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn game_with(rule: &str, size: usize, cells: &[(usize, usize)]) -> Game {
+    Game::from_cell_board(board_with(size, cells)).with_rule(rule.parse().unwrap())
+}
+
+fn run_until_cycle(game: &mut Game, generations: usize) -> Option<Cycle> {
+    for _ in 0..generations {
+        if game.cycle().is_some() {
+            break;
+        }
+        game.tick();
+    }
+    game.cycle()
+}
+
+#[test]
+fn detects_a_blinker() {
+    let mut game = game_with("B3/S23", 8, &[(3, 2), (3, 3), (3, 4)]).with_period_detection();
+    let cycle = run_until_cycle(&mut game, 10).unwrap();
+    assert_eq!(
+        cycle,
+        Cycle {
+            pre_period: 0,
+            period: 2,
+            translation: (0, 0),
+        }
+    );
+    assert!(cycle.is_oscillator());
+}
+
+#[test]
+fn detects_a_glider_with_its_translation() {
+    let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    let mut game = game_with("B3/S23", 16, &glider).with_period_detection();
+    let cycle = run_until_cycle(&mut game, 10).unwrap();
+    assert_eq!(
+        cycle,
+        Cycle {
+            pre_period: 0,
+            period: 4,
+            translation: (1, 1),
+        }
+    );
+    assert!(cycle.is_spaceship());
+
+    let mut universe = SparseUniverse::new("B3/S23".parse().unwrap())
+        .unwrap()
+        .with_period_detection();
+    for (row, col) in glider {
+        universe.set_cell(row as i64 - 100, col as i64 + 100, true);
+    }
+    universe.step(4);
+    assert_eq!(universe.cycle(), None);
+    universe.step(4);
+    assert_eq!(universe.cycle(), Some(cycle));
+}
+
+// an L tromino turns into a block after one generation
+#[test]
+fn detects_a_pre_period() {
+    let mut game = game_with("B3/S23", 8, &[(2, 2), (2, 3), (3, 2)]).with_period_detection();
+    let cycle = run_until_cycle(&mut game, 10).unwrap();
+    assert_eq!(
+        cycle,
+        Cycle {
+            pre_period: 1,
+            period: 1,
+            translation: (0, 0),
+        }
+    );
+    assert!(cycle.is_still_life());
+}
+
+#[test]
+fn compares_states_not_only_positions() {
+    let mut detector = PeriodDetector::default();
+    let cells = [(0, 0), (0, 1)];
+    assert_eq!(
+        detector.observe_states(0, cells.map(|cell| (cell, Cell::Alive))),
+        None
+    );
+    assert_eq!(
+        detector.observe_states(1, cells.map(|cell| (cell, Cell::Dying(1)))),
+        None
+    );
+    let mut copy = detector.clone();
+    assert_eq!(copy.observe(2, cells), None);
+    assert_eq!(
+        copy.observe_states(3, cells.map(|cell| (cell, Cell::Dying(1)))),
+        None
+    );
+    assert_eq!(
+        copy.observe(4, cells),
+        Some(Cycle {
+            pre_period: 0,
+            period: 2,
+            translation: (0, 0),
+        })
+    );
+    assert_eq!(detector.cycle(), None);
+}
+
+// a state whose hash was seen before is only a cycle if it comes back again
+#[test]
+fn waits_for_the_repeated_state_to_come_back() {
+    let mut detector = PeriodDetector::default();
+    assert_eq!(detector.observe(0, [(0, 0)]), None);
+    assert_eq!(detector.observe(1, [(5, 5), (5, 6)]), None);
+    assert_eq!(detector.observe(2, [(0, 0)]), None);
+    assert_eq!(detector.observe(3, [(5, 5), (6, 5)]), None);
+    assert_eq!(detector.observe(4, [(0, 0), (0, 1), (1, 0)]), None);
+    assert_eq!(detector.cycle(), None);
+    assert_eq!(detector.observe(5, [(1, 1), (1, 2), (2, 1)]), None);
+    assert_eq!(
+        detector.observe(6, [(2, 2), (2, 3), (3, 2)]),
+        Some(Cycle {
+            pre_period: 4,
+            period: 1,
+            translation: (1, 1),
+        })
+    );
+}
+
+#[test]
+fn stepping_back_before_the_repeat_forgets_the_cycle() {
+    let mut game = game_with("B3/S23", 8, &[(3, 2), (3, 3), (3, 4)])
+        .with_history(10)
+        .with_period_detection();
+    let cycle = run_until_cycle(&mut game, 10).unwrap();
+    assert_eq!(game.generation(), 4);
+    assert!(game.step_back());
+    assert!(game.step_back());
+    assert_eq!(game.cycle(), Some(cycle));
+    assert!(game.step_back());
+    assert_eq!(game.generation(), 1);
+    assert_eq!(game.cycle(), None);
+    assert_eq!(run_until_cycle(&mut game, 10), Some(cycle));
+}
@@ -0,0 +1,69 @@
+use game_of_life::game::{
+    CellBoard, CellBoardCreationError, FormatErrorVariant, Position, plaintext, rle,
+};
+
+// the variant and position of the format error a parser returned
+fn format_error<T>(
+    result: Result<T, CellBoardCreationError>,
+) -> (FormatErrorVariant, Option<Position>) {
+    match result {
+        Err(CellBoardCreationError::FormatError {
+            variant, position, ..
+        }) => (variant, position),
+        Err(error) => panic!("expected a format error, got {}", error),
+        Ok(_) => panic!("expected a format error"),
+    }
+}
+
+fn position(line: usize, column: usize) -> Option<Position> {
+    Some(Position { line, column })
+}
+
+#[test]
+fn reports_where_rle_is_malformed() {
+    // an unknown character in the body, counted from 1 including the indent
+    let (variant, at) = format_error(rle::parse("#N Glider\nx = 3, y = 3\nbo$2bo$\n  3o?!\n"));
+    assert!(matches!(
+        variant,
+        FormatErrorVariant::UnrecognizedCharacter('?')
+    ));
+    assert_eq!(at, position(4, 5));
+
+    // a header without sizes is reported at the start of its line
+    let (variant, at) = format_error(rle::parse("#C comment\n\n x = 3, rule = B3/S23\no!\n"));
+    assert!(matches!(variant, FormatErrorVariant::InvalidHeader(_)));
+    assert_eq!(at, position(3, 2));
+
+    let (variant, at) = format_error(rle::parse("x = 3, y = 3, rule = B9/S23\no!\n"));
+    assert!(matches!(variant, FormatErrorVariant::InvalidRule(_)));
+    assert_eq!(at, position(1, 1));
+
+    let (variant, at) = format_error(rle::parse("x = 3, y = 3\no$99999999999999999999o!\n"));
+    assert!(matches!(variant, FormatErrorVariant::RunCountOutOfRange));
+    assert_eq!(at, position(2, 22));
+}
+
+#[test]
+fn reports_where_plaintext_is_malformed() {
+    let (variant, at) = format_error(plaintext::parse("!Name: Glider\n.O\n..O\nOOx\n"));
+    assert!(matches!(
+        variant,
+        FormatErrorVariant::UnrecognizedCharacter('x')
+    ));
+    assert_eq!(at, position(4, 3));
+
+    let (variant, at) = format_error(plaintext::parse("!comment\n!another\n\n.O.\n.#.\n"));
+    assert!(matches!(
+        variant,
+        FormatErrorVariant::UnrecognizedCharacter('#')
+    ));
+    assert_eq!(at, position(5, 2));
+}
+
+#[test]
+fn shows_the_position_before_the_message() {
+    let error = CellBoard::from_rle("x = 2, y = 1\n2o\n o%!").err().unwrap();
+    assert_eq!(error.to_string(), "3:3: unrecognized character '%'");
+    let error = CellBoard::from_plaintext("..\n.X").err().unwrap();
+    assert_eq!(error.to_string(), "2:2: unrecognized character 'X'");
+}
@@ -0,0 +1,154 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{CellBoard, CellBoardCreationError, FormatErrorVariant, Position, rle};
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn board_live_cells(cell_board: &CellBoard) -> Vec<(usize, usize)> {
+    cell_board
+        .slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+// the variant and position of the format error a parser returned
+fn format_error<T>(
+    result: Result<T, CellBoardCreationError>,
+) -> (FormatErrorVariant, Option<Position>) {
+    match result {
+        Err(CellBoardCreationError::FormatError {
+            variant, position, ..
+        }) => (variant, position),
+        Err(error) => panic!("expected a format error, got {}", error),
+        Ok(_) => panic!("expected a format error"),
+    }
+}
+
+const GLIDER: &str =
+    "#N Glider\n#C The smallest spaceship\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+
+#[test]
+fn parses_header_comments_and_runs() {
+    let pattern = rle::parse(GLIDER).unwrap();
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.comments, ["The smallest spaceship"]);
+    assert_eq!(pattern.rule, Some(Rule::conway()));
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (3, 3)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+}
+
+#[test]
+fn parses_runs_across_lines_and_blank_rows() {
+    let pattern = rle::parse("x = 12, y = 4\n10b\n2o2$o!").unwrap();
+    assert_eq!(pattern.rule, None);
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (4, 12)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        [(0, 10), (0, 11), (2, 0)]
+    );
+}
+
+#[test]
+fn writes_runs_and_header() {
+    let cell_board = board_with(4, &[(0, 1), (0, 2), (0, 3), (2, 0)]);
+    assert_eq!(
+        rle::write(&cell_board, Rule::conway()),
+        "x = 4, y = 4, rule = B3/S23\nb3o2$o!\n"
+    );
+}
+
+#[test]
+fn splits_long_lines() {
+    let cells: Vec<(usize, usize)> = (0..100).step_by(2).map(|col| (0, col)).collect();
+    let mut cell_board = CellBoard::new(1, 100);
+    for &(row, col) in &cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    let written = rle::write(&cell_board, Rule::conway());
+    assert!(written.lines().all(|line| line.len() <= 70));
+    assert_eq!(
+        board_live_cells(&rle::parse(&written).unwrap().cell_board),
+        cells
+    );
+}
+
+#[test]
+fn round_trips() {
+    let cell_board = board_with(
+        9,
+        &[
+            (0, 0),
+            (0, 8),
+            (3, 3),
+            (3, 4),
+            (3, 5),
+            (4, 2),
+            (8, 0),
+            (8, 8),
+        ],
+    );
+    let rule: Rule = "B36/S23".parse().unwrap();
+    let pattern = rle::parse(&rle::write(&cell_board, rule)).unwrap();
+    assert_eq!(pattern.rule, Some(rule));
+    assert_eq!(
+        (pattern.cell_board.height(), pattern.cell_board.width()),
+        (9, 9)
+    );
+    assert_eq!(
+        board_live_cells(&pattern.cell_board),
+        board_live_cells(&cell_board)
+    );
+}
+
+#[test]
+fn rejects_run_counts_out_of_range() {
+    for input in [
+        "99999999999999999999999o!",
+        "18446744073709551615b2o!",
+        "18446744073709551615$18446744073709551615$o!",
+    ] {
+        assert!(
+            matches!(
+                format_error(rle::parse(input)).0,
+                FormatErrorVariant::RunCountOutOfRange
+            ),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn rejects_patterns_too_large_for_a_board() {
+    for input in [
+        "1000000000o!",
+        "1000000$1000000bo!",
+        "x = 1000000000, y = 1000000000\no!",
+    ] {
+        assert!(
+            matches!(
+                format_error(rle::parse(input)).0,
+                FormatErrorVariant::PatternTooLarge { .. }
+            ),
+            "{}",
+            input
+        );
+    }
+}
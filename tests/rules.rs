@@ -0,0 +1,73 @@
+use game_of_life::game::rule::{Rule, RuleParseError};
+
+#[test]
+fn parses_birth_survival_notation() {
+    for notation in ["B36/S23", "b36/s23", "B36S23", " B36/S23 "] {
+        assert_eq!(
+            notation.parse::<Rule>(),
+            Rule::new(&[3, 6], &[2, 3]),
+            "{}",
+            notation
+        );
+    }
+    assert_eq!("B2/S".parse::<Rule>(), Rule::new(&[2], &[]));
+    assert_eq!(
+        "B/S012345678".parse::<Rule>(),
+        Rule::new(&[], &[0, 1, 2, 3, 4, 5, 6, 7, 8])
+    );
+    assert_eq!("B3/S23".parse::<Rule>(), Ok(Rule::conway()));
+}
+
+#[test]
+fn parses_survival_birth_notation() {
+    assert_eq!("23/3".parse::<Rule>(), Ok(Rule::conway()));
+    assert_eq!("23/36".parse::<Rule>(), Rule::new(&[3, 6], &[2, 3]));
+    assert_eq!("/2".parse::<Rule>(), Rule::new(&[2], &[]));
+    assert_eq!("S23/B3".parse::<Rule>(), Ok(Rule::conway()));
+    assert_eq!(
+        "345/2/4".parse::<Rule>(),
+        Rule::generations(&[2], &[3, 4, 5], 4)
+    );
+}
+
+#[test]
+fn born_and_survives_follow_the_counts() {
+    let rule: Rule = "B36/S23".parse().unwrap();
+    let born: Vec<usize> = (0..=8).filter(|&count| rule.born(count)).collect();
+    let survives: Vec<usize> = (0..=8).filter(|&count| rule.survives(count)).collect();
+    assert_eq!(born, [3, 6]);
+    assert_eq!(survives, [2, 3]);
+}
+
+#[test]
+fn displays_in_birth_survival_notation() {
+    for (notation, displayed) in [
+        ("23/3", "B3/S23"),
+        ("b36s23", "B36/S23"),
+        ("B/S", "B/S"),
+        ("345/2/4", "B2/S345/C4"),
+    ] {
+        let rule: Rule = notation.parse().unwrap();
+        assert_eq!(rule.to_string(), displayed);
+        assert_eq!(displayed.parse::<Rule>(), Ok(rule));
+    }
+}
+
+#[test]
+fn rejects_invalid_rules() {
+    for (notation, error) in [
+        ("", RuleParseError::MissingSeparator),
+        ("B3", RuleParseError::MissingSeparator),
+        ("233", RuleParseError::MissingSeparator),
+        ("S23", RuleParseError::MissingSeparator),
+        ("S23/3", RuleParseError::MissingSeparator),
+        ("B3/S2x", RuleParseError::UnexpectedCharacter('x')),
+        ("B3?/S23", RuleParseError::UnexpectedCharacter('?')),
+        ("B9/S23", RuleParseError::NeighborCountOutOfRange(9)),
+        ("23/39", RuleParseError::NeighborCountOutOfRange(9)),
+        ("B2/S/C1", RuleParseError::StateCountOutOfRange(1)),
+        ("B2/S/C256", RuleParseError::StateCountOutOfRange(256)),
+    ] {
+        assert_eq!(notation.parse::<Rule>(), Err(error), "{:?}", notation);
+    }
+}
@@ -0,0 +1,98 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::topology::Topology;
+use game_of_life::game::{CellBoard, Game, PatternFormat};
+
+// a square board of the given size with the listed cells alive
+fn board_with(size: usize, cells: &[(usize, usize)]) -> CellBoard {
+    let mut cell_board = CellBoard::new(size, size);
+    for &(row, col) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Alive);
+    }
+    cell_board
+}
+
+fn game_with(rule: &str, size: usize, cells: &[(usize, usize)]) -> Game {
+    Game::from_cell_board(board_with(size, cells)).with_rule(rule.parse().unwrap())
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+const FORMATS: [(PatternFormat, &str); 6] = [
+    (PatternFormat::Life, "life"),
+    (PatternFormat::Rle, "rle"),
+    (PatternFormat::Plaintext, "cells"),
+    (PatternFormat::Life105, "lif"),
+    (PatternFormat::Life106, "lif"),
+    (PatternFormat::Macrocell, "mc"),
+];
+
+// saves the game the way the save key does and loads the file again
+fn reload(game: &Game, format: PatternFormat, extension: &str, name: &str) -> Game {
+    let path = std::env::temp_dir().join(format!(
+        "life-save-{}-{}-{:?}.{}",
+        std::process::id(),
+        name,
+        format,
+        extension
+    ));
+    std::fs::write(&path, game.to_pattern(format).unwrap()).unwrap();
+    let reloaded = Game::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    reloaded
+}
+
+fn board_of(game: &Game) -> (usize, usize, Vec<(usize, usize)>) {
+    (game.height(), game.width(), live_cells(game))
+}
+
+#[test]
+fn every_format_keeps_the_board() {
+    let glider = [(3, 5), (4, 6), (5, 4), (5, 5), (5, 6)];
+    let mut game = game_with("B36/S23", 17, &glider).with_topology(Topology::KleinBottle);
+    for _ in 0..5 {
+        game.tick();
+    }
+    for (format, extension) in FORMATS {
+        let reloaded = reload(&game, format, extension, "glider");
+        assert_eq!(board_of(&reloaded), board_of(&game), "{:?}", format);
+        // the X/O grid has no place for anything but the cells
+        if format != PatternFormat::Life {
+            assert_eq!(reloaded.generation(), 5, "{:?}", format);
+            assert_eq!(reloaded.rule(), game.rule(), "{:?}", format);
+            assert_eq!(reloaded.topology(), Topology::KleinBottle, "{:?}", format);
+        }
+    }
+}
+
+#[test]
+fn every_format_keeps_the_size_of_an_empty_board() {
+    let mut game = Game::new(9, 14);
+    game.tick();
+    for (format, extension) in FORMATS {
+        let reloaded = reload(&game, format, extension, "empty");
+        assert_eq!(board_of(&reloaded), (9, 14, Vec::new()), "{:?}", format);
+    }
+}
+
+#[test]
+fn only_marked_comments_are_read_as_metadata() {
+    let game = Game::from_rle(
+        "#C generation: 40\n#C topology: plane\n#C size: 30x30\nx = 2, y = 1\n2o!\n",
+    )
+    .unwrap();
+    assert_eq!(game.generation(), 0);
+    assert_eq!(game.topology(), Topology::Torus);
+    assert_eq!(board_of(&game), (1, 2, vec![(0, 0), (0, 1)]));
+
+    let game = Game::from_rle(
+        "#C [game-of-life] generation: 40\n#C [game-of-life] topology: plane\nx = 2, y = 1\n2o!\n",
+    )
+    .unwrap();
+    assert_eq!(game.generation(), 40);
+    assert_eq!(game.topology(), Topology::Plane);
+}
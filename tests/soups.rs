@@ -0,0 +1,151 @@
+use game_of_life::game::Game;
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::soup::{Gradient, Region, Soup, SoupError, Symmetry};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fn live_cells(game: &Game) -> Vec<Slot> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(slot, _)| slot)
+        .collect()
+}
+
+#[test]
+fn seeded_soups_repeat() {
+    let soup = Game::new(32, 32).randomize_seeded(0.4, 7);
+    assert_eq!(
+        live_cells(&soup),
+        live_cells(&Game::new(32, 32).randomize_seeded(0.4, 7))
+    );
+    assert_ne!(
+        live_cells(&soup),
+        live_cells(&Game::new(32, 32).randomize_seeded(0.4, 8))
+    );
+    let with_rng = Game::new(32, 32).randomize_with(0.4, &mut ChaCha8Rng::seed_from_u64(7));
+    assert_eq!(live_cells(&soup), live_cells(&with_rng));
+}
+
+// a seed names the same soup on every platform, so that a seed from a title
+// or a census can be rerun anywhere
+#[test]
+fn seeded_soups_are_portable() {
+    assert_eq!(
+        Game::new(4, 8).randomize_seeded(0.5, 7).to_life(),
+        "XXOOOXXO\nXOXXOXXO\nXXXXXOXX\nXXOXXOOX\n"
+    );
+}
+
+#[test]
+fn seeded_species_repeat() {
+    let rule = "QuadLife".parse().unwrap();
+    let soup = |seed| {
+        Game::new(16, 16)
+            .with_rule(rule)
+            .randomize_seeded(0.5, seed)
+            .slots_and_cells()
+            .collect::<Vec<(Slot, Cell)>>()
+    };
+    assert_eq!(soup(3), soup(3));
+    assert!(soup(3).iter().any(|(_, cell)| *cell == Cell::Species(4)));
+}
+
+fn cells(game: &Game) -> Vec<Vec<bool>> {
+    (0..game.height())
+        .map(|row| {
+            (0..game.width())
+                .map(|col| game.get_slot(Slot(row, col)).is_alive())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn symmetric_soups_are_symmetric() {
+    for symmetry in Symmetry::ALL {
+        let soup = Soup::new(0.5).with_symmetry(symmetry);
+        let board = cells(&Game::new(15, 15).randomize_soup_seeded(&soup, 1).unwrap());
+        let at = |row: usize, col: usize| board[row][col];
+        for row in 0..15 {
+            for col in 0..15 {
+                let (mirrored_row, mirrored_col) = (14 - row, 14 - col);
+                let expected = match symmetry {
+                    Symmetry::C1 => vec![],
+                    Symmetry::C2 => vec![at(mirrored_row, mirrored_col)],
+                    Symmetry::C4 => vec![at(col, mirrored_row), at(mirrored_row, mirrored_col)],
+                    Symmetry::D2 => vec![at(row, mirrored_col)],
+                    Symmetry::D4 => vec![at(row, mirrored_col), at(mirrored_row, col)],
+                    Symmetry::D8 => {
+                        vec![at(col, row), at(col, mirrored_row), at(row, mirrored_col)]
+                    }
+                };
+                for image in expected {
+                    assert_eq!(image, at(row, col), "{} at {},{}", symmetry, row, col);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn soups_stay_in_their_region() {
+    let region: Region = "4,6,8,10".parse().unwrap();
+    let soup = Soup::new(1.0)
+        .with_region(region)
+        .with_symmetry(Symmetry::D4);
+    let game = Game::new(20, 20).randomize_soup_seeded(&soup, 1).unwrap();
+    let live = live_cells(&game);
+    assert_eq!(live.len(), 80);
+    assert!(
+        live.iter()
+            .all(|Slot(row, col)| (4..12).contains(row) && (6..16).contains(col))
+    );
+
+    assert_eq!(
+        Game::new(10, 10).randomize_soup_seeded(&soup, 1).err(),
+        Some(SoupError::RegionOutOfBounds {
+            region,
+            height: 10,
+            width: 10
+        })
+    );
+    assert_eq!(
+        Game::new(20, 20)
+            .randomize_soup_seeded(&soup.with_symmetry(Symmetry::C4), 1)
+            .err(),
+        Some(SoupError::NonSquareRegion(Symmetry::C4))
+    );
+    let far = Region {
+        row: usize::MAX,
+        col: 0,
+        height: 2,
+        width: 2,
+    };
+    assert_eq!(
+        Soup::new(0.5).with_region(far).region_on(10, 10),
+        Err(SoupError::RegionOutOfBounds {
+            region: far,
+            height: 10,
+            width: 10
+        })
+    );
+    assert!("4,6,0,10".parse::<Region>().is_err());
+    assert!("4,6,8".parse::<Region>().is_err());
+    assert_eq!("d8".parse::<Symmetry>(), Ok(Symmetry::D8));
+    assert!("C3".parse::<Symmetry>().is_err());
+}
+
+#[test]
+fn gradients_change_the_density() {
+    let soup = Soup::new(1.0).with_gradient(Gradient::Horizontal, 0.0);
+    assert_eq!(soup.density_at(3, 0, 8, 11), 1.0);
+    assert_eq!(soup.density_at(3, 5, 8, 11), 0.5);
+    assert_eq!(soup.density_at(3, 10, 8, 11), 0.0);
+    let board = cells(&Game::new(8, 11).randomize_soup_seeded(&soup, 1).unwrap());
+    assert!(board.iter().all(|row| row[0] && !row[10]));
+
+    let radial = Soup::new(0.0).with_gradient(Gradient::Radial, 1.0);
+    assert_eq!(radial.density_at(4, 4, 9, 9), 0.0);
+    assert_eq!(radial.density_at(0, 8, 9, 9), 1.0);
+    assert!(radial.density_at(0, 4, 9, 9) < radial.density_at(0, 0, 9, 9));
+}
@@ -0,0 +1,94 @@
+use game_of_life::game::cell::Slot;
+use game_of_life::game::soup::{Region, Soup};
+use game_of_life::game::{CellBoardCreationError, FormatErrorVariant, Game};
+use game_of_life::sparse::SparseUniverse;
+
+fn live_cells(game: &Game) -> Vec<(i64, i64)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row as i64, col as i64))
+        .collect()
+}
+
+// a soup in the middle of a large board does not reach its edges within the
+// generations compared, so the bounded and the unbounded universe agree
+#[test]
+fn matches_game_tick_away_from_the_edges() {
+    let soup = Soup::new(0.5).with_region(Region {
+        row: 40,
+        col: 40,
+        height: 16,
+        width: 16,
+    });
+    for (rule, seed) in [("B3/S23", 1), ("B36/S23", 2), ("B3678/S34678", 3)] {
+        let mut game = Game::new(96, 96)
+            .with_rule(rule.parse().unwrap())
+            .randomize_soup_seeded(&soup, seed)
+            .unwrap();
+        let mut universe = SparseUniverse::new(game.rule()).unwrap();
+        for (row, col) in live_cells(&game) {
+            universe.set_cell(row, col, true);
+        }
+        for _ in 0..36 {
+            game.tick();
+            universe.tick();
+            let mut sparse: Vec<(i64, i64)> = universe.live_cells().collect();
+            sparse.sort_unstable();
+            assert_eq!(sparse, live_cells(&game), "{} from seed {}", rule, seed);
+        }
+    }
+}
+
+// far from the origin, in negative and positive coordinates, a glider keeps
+// its shape and moves one cell diagonally every four generations
+#[test]
+fn glider_travels_far_off_origin() {
+    const GLIDER: [(i64, i64); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    for (top, left) in [
+        (0, 0),
+        (-3_000_000_000_000, 7_000_000_000),
+        (4_000_000_000_000_000, -4_000_000_000_000_000),
+    ] {
+        let mut universe = SparseUniverse::new("B3/S23".parse().unwrap()).unwrap();
+        for (row, col) in GLIDER {
+            universe.set_cell(top + row, left + col, true);
+        }
+        universe.step(4000);
+        let mut cells: Vec<(i64, i64)> = universe.live_cells().collect();
+        cells.sort_unstable();
+        let expected: Vec<(i64, i64)> = GLIDER
+            .iter()
+            .map(|(row, col)| (top + 1000 + row, left + 1000 + col))
+            .collect();
+        assert_eq!(cells, expected, "from ({}, {})", top, left);
+        assert_eq!(universe.generation(), 4000);
+    }
+}
+
+#[test]
+fn refuses_boards_too_large_to_hold() {
+    let mut universe = SparseUniverse::new("B3/S23".parse().unwrap()).unwrap();
+    let (cell_board, _) = universe.to_cell_board().unwrap();
+    assert_eq!((cell_board.height(), cell_board.width()), (0, 0));
+    universe.set_cell(-2, 5, true);
+    universe.set_cell(1, 6, true);
+    let (cell_board, origin) = universe.to_cell_board().unwrap();
+    assert_eq!((cell_board.height(), cell_board.width()), (4, 2));
+    assert_eq!(origin, (-2, 5));
+
+    universe.set_cell(i64::MIN, i64::MIN, true);
+    universe.set_cell(i64::MAX, i64::MAX, true);
+    let bounding_box = universe.bounding_box().unwrap();
+    assert_eq!(bounding_box.height(), u64::MAX);
+    assert_eq!(bounding_box.width(), u64::MAX);
+    assert!(matches!(
+        universe.to_cell_board(),
+        Err(CellBoardCreationError::FormatError {
+            variant: FormatErrorVariant::PatternTooLarge {
+                height: u64::MAX,
+                width: u64::MAX,
+            },
+            ..
+        })
+    ));
+}
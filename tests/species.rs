@@ -0,0 +1,103 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::rule::Rule;
+use game_of_life::game::{
+    CellBoard, CellBoardCreationError, FormatErrorVariant, Game, Position, rle,
+};
+
+fn game_with(rule: &str, cells: &[(usize, usize, u8)]) -> Game {
+    let mut cell_board = CellBoard::new(16, 16);
+    for &(row, col, species) in cells {
+        cell_board.set_slot(Slot(row, col), Cell::Species(species));
+    }
+    Game::from_cell_board(cell_board).with_rule(rule.parse().unwrap())
+}
+
+fn live_cells(game: &Game) -> Vec<(usize, usize, u8)> {
+    game.slots_and_cells()
+        .filter_map(|(Slot(row, col), cell)| Some((row, col, cell.species()?)))
+        .collect()
+}
+
+// the variant and position of the format error a parser returned
+fn format_error<T>(
+    result: Result<T, CellBoardCreationError>,
+) -> (FormatErrorVariant, Option<Position>) {
+    match result {
+        Err(CellBoardCreationError::FormatError {
+            variant, position, ..
+        }) => (variant, position),
+        Err(error) => panic!("expected a format error, got {}", error),
+        Ok(_) => panic!("expected a format error"),
+    }
+}
+
+#[test]
+fn multi_color_rules_round_trip() {
+    for (notation, species) in [
+        ("Immigration", 2),
+        ("QuadLife", 4),
+        ("Immigration:B36/S23", 2),
+    ] {
+        let rule: Rule = notation.parse().unwrap();
+        assert_eq!(rule.species(), species);
+        assert!(!rule.is_life_like());
+        assert_eq!(rule.to_string(), notation);
+    }
+    assert_eq!("quadlife".parse::<Rule>().unwrap().species(), 4);
+    assert!("Immigration:B2/S/C3".parse::<Rule>().is_err());
+    assert!(Rule::conway().with_species(3).is_err());
+}
+
+// the cells born beside a blinker have all three of its cells as parents and
+// take the species two of them share
+#[test]
+fn immigration_newborns_take_the_majority_species() {
+    let mut game = game_with("Immigration", &[(6, 7, 1), (7, 7, 1), (8, 7, 2)]);
+    game.tick();
+    assert_eq!(live_cells(&game), [(7, 6, 1), (7, 7, 1), (7, 8, 1)]);
+    game.tick();
+    assert_eq!(live_cells(&game), [(6, 7, 1), (7, 7, 1), (8, 7, 1)]);
+}
+
+// a cell born of three parents of different species takes the fourth
+#[test]
+fn quadlife_newborns_of_three_species_take_the_fourth() {
+    let mut game = game_with("QuadLife", &[(6, 7, 1), (7, 7, 2), (8, 7, 3)]);
+    game.tick();
+    assert_eq!(live_cells(&game), [(7, 6, 4), (7, 7, 2), (7, 8, 4)]);
+    assert_eq!(game.species_population(), [0, 1, 0, 2]);
+}
+
+#[test]
+fn glider_keeps_its_species() {
+    let glider = [(0, 1, 2), (1, 2, 2), (2, 0, 2), (2, 1, 2), (2, 2, 2)];
+    let mut game = game_with("Immigration", &glider);
+    for _ in 0..4 {
+        game.tick();
+    }
+    let moved: Vec<_> = glider
+        .iter()
+        .map(|&(row, col, species)| (row + 1, col + 1, species))
+        .collect();
+    assert_eq!(live_cells(&game), moved);
+    assert_eq!(game.species_population(), [0, 5]);
+}
+
+#[test]
+fn species_survive_rle() {
+    let game = game_with("QuadLife", &[(1, 1, 1), (1, 2, 2), (2, 1, 3), (2, 2, 4)]);
+    let loaded = Game::from_rle(&game.to_rle()).unwrap();
+    assert_eq!(loaded.rule(), game.rule());
+    assert_eq!(
+        live_cells(&loaded),
+        [(1, 1, 1), (1, 2, 2), (2, 1, 3), (2, 2, 4)]
+    );
+}
+
+#[test]
+fn rle_rejects_states_past_the_species() {
+    let (variant, position) = format_error(rle::parse("x = 3, y = 1, rule = Immigration\nABC!"));
+    assert!(matches!(variant, FormatErrorVariant::StateOutOfRange(3)));
+    assert_eq!(position, Some(Position { line: 2, column: 3 }));
+    assert!(rle::parse("x = 4, y = 1, rule = QuadLife\nABCD!").is_ok());
+}
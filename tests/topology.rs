@@ -0,0 +1,182 @@
+use game_of_life::game::cell::{Cell, Slot};
+use game_of_life::game::topology::Topology;
+use game_of_life::game::{CellBoard, Game};
+
+fn live_cells(game: &Game) -> Vec<(usize, usize)> {
+    game.slots_and_cells()
+        .filter(|(_, cell)| cell.is_alive())
+        .map(|(Slot(row, col), _)| (row, col))
+        .collect()
+}
+
+// a glider heading down and to the right, one cell diagonally every four
+// generations
+const GLIDER: [(usize, usize); 5] = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+
+fn glider_game(topology: Topology, height: usize, width: usize, top: usize, left: usize) -> Game {
+    let mut cell_board = CellBoard::new(height, width);
+    for (row, col) in GLIDER {
+        cell_board.set_slot(Slot(top + row, left + col), Cell::Alive);
+    }
+    Game::from_cell_board(cell_board).with_topology(topology)
+}
+
+fn run(game: &mut Game, generations: usize) {
+    for _ in 0..generations {
+        game.tick();
+    }
+}
+
+// the glider after `shift` cells of travel from (top, left), with each cell
+// placed by `place` from its position on the unbounded plane
+fn glider_at(
+    top: usize,
+    left: usize,
+    shift: usize,
+    place: impl Fn(usize, usize) -> (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut cells: Vec<(usize, usize)> = GLIDER
+        .iter()
+        .map(|&(row, col)| place(top + shift + row, left + shift + col))
+        .collect();
+    cells.sort_unstable();
+    cells
+}
+
+fn neighbor(
+    topology: Topology,
+    slot: (usize, usize),
+    offset: (i32, i32),
+) -> Option<(usize, usize)> {
+    topology
+        .neighbor(Slot(slot.0, slot.1), offset, 4, 5)
+        .map(|Slot(row, col)| (row, col))
+}
+
+#[test]
+fn maps_neighbors_across_the_edges() {
+    use Topology::*;
+    // on a board of 4 rows and 5 columns
+    for (topology, slot, offset, expected) in [
+        (Torus, (0, 0), (-1, -1), Some((3, 4))),
+        (Torus, (3, 4), (1, 1), Some((0, 0))),
+        (Plane, (0, 0), (-1, 0), None),
+        (Plane, (0, 0), (0, -1), None),
+        (Plane, (3, 4), (1, 1), None),
+        (Plane, (0, 0), (1, 1), Some((1, 1))),
+        (HorizontalCylinder, (2, 0), (0, -1), Some((2, 4))),
+        (HorizontalCylinder, (2, 4), (1, 1), Some((3, 0))),
+        (HorizontalCylinder, (0, 2), (-1, 0), None),
+        (HorizontalCylinder, (3, 2), (1, 0), None),
+        (VerticalCylinder, (0, 2), (-1, 0), Some((3, 2))),
+        (VerticalCylinder, (3, 2), (1, 1), Some((0, 3))),
+        (VerticalCylinder, (2, 0), (0, -1), None),
+        (VerticalCylinder, (2, 4), (0, 1), None),
+        // top and bottom are joined with the columns mirrored
+        (KleinBottle, (0, 1), (-1, 0), Some((3, 3))),
+        (KleinBottle, (3, 0), (1, 0), Some((0, 4))),
+        (KleinBottle, (3, 1), (1, 1), Some((0, 2))),
+        (KleinBottle, (2, 4), (0, 1), Some((2, 0))),
+        (KleinBottle, (0, 0), (-1, -1), Some((3, 0))),
+        // left and right are joined with the rows mirrored as well
+        (CrossSurface, (0, 1), (-1, 0), Some((3, 3))),
+        (CrossSurface, (1, 0), (0, -1), Some((2, 4))),
+        (CrossSurface, (3, 4), (0, 1), Some((0, 0))),
+        (CrossSurface, (0, 0), (-1, -1), Some((0, 0))),
+    ] {
+        assert_eq!(
+            neighbor(topology, slot, offset),
+            expected,
+            "{} from {:?} by {:?}",
+            topology,
+            slot,
+            offset
+        );
+    }
+}
+
+#[test]
+fn interior_neighbors_agree_on_every_topology() {
+    for topology in Topology::ALL {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                assert_eq!(
+                    neighbor(topology, (1, 2), (dy, dx)),
+                    Some(((1 + dy) as usize, (2 + dx) as usize))
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn glider_returns_on_a_torus() {
+    let mut game = glider_game(Topology::Torus, 8, 8, 2, 3);
+    let start = live_cells(&game);
+    run(&mut game, 32);
+    assert_eq!(live_cells(&game), start);
+}
+
+// a glider crashing into the corner of dead borders settles into a block
+#[test]
+fn glider_dies_on_a_plane() {
+    let mut game = glider_game(Topology::Plane, 10, 10, 3, 3);
+    run(&mut game, 40);
+    assert_eq!(live_cells(&game), [(8, 8), (8, 9), (9, 8), (9, 9)]);
+}
+
+#[test]
+fn glider_crosses_the_joined_edges_of_cylinders() {
+    // left and right joined: the glider leaves to the right and enters on the left
+    let mut game = glider_game(Topology::HorizontalCylinder, 16, 8, 1, 3);
+    run(&mut game, 24);
+    assert_eq!(
+        live_cells(&game),
+        glider_at(1, 3, 6, |row, col| (row, col % 8))
+    );
+    // and settles into a block against the dead bottom edge
+    run(&mut game, 40);
+    let cells = live_cells(&game);
+    assert_eq!(cells.len(), 4);
+    assert!(cells.iter().all(|&(row, _)| row >= 14));
+
+    // top and bottom joined: the glider leaves at the bottom and enters at the top
+    let mut game = glider_game(Topology::VerticalCylinder, 8, 16, 3, 1);
+    run(&mut game, 24);
+    assert_eq!(
+        live_cells(&game),
+        glider_at(3, 1, 6, |row, col| (row % 8, col))
+    );
+    // and settles into a block against the dead right edge
+    run(&mut game, 40);
+    let cells = live_cells(&game);
+    assert_eq!(cells.len(), 4);
+    assert!(cells.iter().all(|&(_, col)| col >= 14));
+}
+
+#[test]
+fn glider_crosses_twisted_edges_mirrored() {
+    // leaving at the bottom of a Klein bottle enters at the top of the mirrored
+    // column, so the glider comes back heading down and to the left
+    let mut game = glider_game(Topology::KleinBottle, 8, 16, 3, 2);
+    run(&mut game, 32);
+    assert_eq!(
+        live_cells(&game),
+        glider_at(3, 2, 8, |row, col| (row - 8, 15 - col))
+    );
+
+    // a cross surface mirrors the rows of a glider leaving at the right
+    let mut game = glider_game(Topology::CrossSurface, 16, 8, 2, 3);
+    run(&mut game, 32);
+    assert_eq!(
+        live_cells(&game),
+        glider_at(2, 3, 8, |row, col| (15 - row, col - 8))
+    );
+    // and the columns of one leaving at the bottom, like a Klein bottle
+    let mut game = glider_game(Topology::CrossSurface, 8, 16, 3, 2);
+    run(&mut game, 32);
+    assert_eq!(
+        live_cells(&game),
+        glider_at(3, 2, 8, |row, col| (row - 8, 15 - col))
+    );
+}